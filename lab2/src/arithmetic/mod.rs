@@ -0,0 +1,207 @@
+//! Integer helpers on `T` that do not overflow for moduli below 2^127.
+
+use num_bigint::BigUint;
+
+use crate::T;
+
+/// Full 256-bit product of two `T` values, returned as (high, low) halves.
+pub const fn widening_mul(a: T, b: T) -> (T, T) {
+    const MASK: T = u64::MAX as T;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (hi_lo & MASK) + (lo_hi & MASK);
+    let lo = (middle << 64) | (lo_lo & MASK);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+    (hi, lo)
+}
+
+/// Returns `a * b mod m` for `a, b < m < 2^127`.
+/// When the product fits in `T` this is a single multiplication, otherwise the
+/// 256-bit product is reduced in chunks as wide as the headroom above `m` allows.
+pub const fn mul_mod(a: T, b: T, m: T) -> T {
+    if a.leading_zeros() + b.leading_zeros() >= T::BITS {
+        return a * b % m;
+    }
+
+    assert!(m.leading_zeros() > 0, "Modulus must be smaller than 2^127");
+    let (hi, lo) = widening_mul(a, b);
+    let chunk = m.leading_zeros();
+    let mut result = hi % m;
+    let mut remaining = T::BITS;
+    while remaining > 0 {
+        let shift = if chunk < remaining { chunk } else { remaining };
+        let bits = (lo >> (remaining - shift)) & ((1 << shift) - 1);
+        result = ((result << shift) | bits) % m;
+        remaining -= shift;
+    }
+    result
+}
+
+/// Returns `base^exp mod m` for `m < 2^127`.
+pub const fn pow_mod(mut base: T, mut exp: T, m: T) -> T {
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp /= 2;
+    }
+    result
+}
+
+/// Returns `a - b mod n` for any `a, b` and `n < 2^127`.
+pub const fn sub_mod(a: T, b: T, n: T) -> T {
+    (a % n + n - b % n) % n
+}
+
+/// Inverse of `a` modulo `n < 2^127` by the extended Euclidean algorithm,
+/// `None` unless a and n are coprime.
+pub const fn inverse_mod(a: T, n: T) -> Option<T> {
+    let (mut r0, mut r1) = (n as i128, (a % n) as i128);
+    let (mut s0, mut s1) = (0_i128, 1_i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 - q * s1);
+    }
+    match r0 {
+        1 => Some(s0.rem_euclid(n as i128) as T),
+        _ => None,
+    }
+}
+
+pub const fn gcd(mut a: T, mut b: T) -> T {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Prime factorization by trial division, as (prime, exponent) pairs in increasing order.
+/// Meant for numbers whose second largest prime factor is small, like orders of small groups.
+pub fn factor(mut n: T) -> Vec<(T, u32)> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    // Stops early once the rest is prime, so only the second largest factor costs time
+    let mut rest_is_prime = is_probable_prime(&BigUint::from(n));
+    while p * p <= n && !rest_is_prime {
+        let mut exponent = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((p, exponent));
+            rest_is_prime = is_probable_prime(&BigUint::from(n));
+        }
+        p += if p == 2 { 1 } else { 2 };
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+/// Miller–Rabin with the primes up to 37 as bases, which makes it exact below 3.3 * 10^24.
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    const BASES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if let Some(&p) = BASES.iter().find(|&&p| n % p == BigUint::ZERO) {
+        return *n == BigUint::from(p);
+    }
+    if *n < BigUint::from(2_u8) {
+        return false;
+    }
+
+    let n_minus_one = n - 1_u8;
+    let s = n_minus_one.trailing_zeros().unwrap();
+    let d = &n_minus_one >> s;
+    BASES.iter().all(|&base| {
+        let mut x = BigUint::from(base).modpow(&d, n);
+        if x == BigUint::from(1_u8) || x == n_minus_one {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = &x * &x % n;
+            x == n_minus_one
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_widening_mul() {
+        assert_eq!(widening_mul(T::MAX, T::MAX), (T::MAX - 1, 1));
+        assert_eq!(widening_mul(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(widening_mul(12345, 678910), (0, 12345 * 678910));
+    }
+
+    #[test]
+    fn test_mul_mod() {
+        let m: T = (1 << 127) - 1;
+        let mut x: T = 0x0123456789abcdef0123456789abcdef;
+        for _ in 0..100 {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let (a, b) = (x % m, x.rotate_left(17) % m);
+            let expected = BigUint::from(a) * BigUint::from(b) % BigUint::from(m);
+
+            assert_eq!(BigUint::from(mul_mod(a, b, m)), expected);
+        }
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        let m: T = (1 << 89) - 1;
+
+        assert_eq!(pow_mod(3, m - 1, m), 1);
+        assert_eq!(pow_mod(2, 89, m), 1);
+        assert_eq!(pow_mod(5, 0, 1), 0);
+    }
+
+    #[test]
+    fn test_sub_and_inverse_mod() {
+        let m: T = (1 << 127) - 1;
+
+        assert_eq!(sub_mod(3, 5, 7), 5);
+        assert_eq!(sub_mod(12, 5, 7), 0);
+        assert_eq!(sub_mod(0, 1, m), m - 1);
+        assert_eq!(inverse_mod(3, 7), Some(5));
+        assert_eq!(mul_mod(inverse_mod(12345, m).unwrap(), 12345, m), 1);
+        assert_eq!(inverse_mod(6, 9), None);
+        assert_eq!(inverse_mod(0, 1), Some(0));
+    }
+
+    #[test]
+    fn test_factor() {
+        assert_eq!(factor(1), vec![]);
+        assert_eq!(factor(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factor(4 * 1_000_000_007), vec![(2, 2), (1_000_000_007, 1)]);
+        assert_eq!(factor(65537 * 65537), vec![(65537, 2)]);
+        assert_eq!(gcd(84, 36), 12);
+    }
+
+    #[test]
+    fn test_is_probable_prime() {
+        let primes: [T; 5] = [2, 37, 65537, (1 << 61) - 1, (1 << 127) - 1];
+        for p in primes {
+            assert!(is_probable_prime(&BigUint::from(p)));
+        }
+        // 3215031751 is a strong pseudoprime to the bases 2, 3, 5 and 7
+        let composites: [T; 5] = [0, 1, 65537 * 65539, 3215031751, (1 << 61) + 1];
+        for n in composites {
+            assert!(!is_probable_prime(&BigUint::from(n)));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use super::NamedCurve;
+use crate::{
+    T,
+    elliptic_curve::Ec,
+    f2m::{F2m, bit::Bits8},
+    traits::FieldBytes,
+};
+
+/// One of GF(2^M) reduced by the polynomial with the given exponents besides M.
+fn field<const M: T>(exponents: &[usize]) -> F2m<M> {
+    let mut modulo = vec![Bits8(0); M as usize / 8 + 1];
+    for i in exponents.iter().chain([&(M as usize)]) {
+        modulo[i / 8].0 |= 1 << (i % 8);
+    }
+    F2m::new_from_slice(&[Bits8(1)], &modulo)
+}
+
+/// Field element from big-endian hexadecimal, in the same field as `one`.
+fn element<const M: T>(one: &F2m<M>, hex: &str) -> F2m<M> {
+    let bytes = hex::decode(hex).expect("Constants must be hexadecimal");
+    one.parse_fixed_bytes(&bytes)
+        .expect("Constants must be elements of the field")
+}
+
+/// Curve with the given a and b of the SEC 2 field GF(2^M), with its base point.
+fn curve<const M: T>(
+    name: &'static str,
+    exponents: &[usize],
+    (a, b): (&str, &str),
+    (x, y): (&str, &str),
+    n: &str,
+    h: u32,
+) -> NamedCurve<F2m<M>> {
+    let one = field::<M>(exponents);
+    let ec = Ec::new(element(&one, a), element(&one, b));
+    let g = (element(&one, x), element(&one, y));
+    NamedCurve::new(name, ec, g, n, h)
+}
+
+/// sect113r1, reduced by x^113 + x^9 + 1. Too weak for anything but experiments.
+pub fn sect113r1() -> NamedCurve<F2m<113>> {
+    curve(
+        "sect113r1",
+        &[9, 0],
+        (
+            "003088250ca6e7c7fe649ce85820f7",
+            "00e8bee4d3e2260744188be0e9c723",
+        ),
+        (
+            "009d73616f35f4ab1407d73562c10f",
+            "00a52830277958ee84d1315ed31886",
+        ),
+        "0100000000000000d9ccec8a39e56f",
+        2,
+    )
+}
+
+/// Koblitz curve sect163k1 (NIST K-163), reduced by x^163 + x^7 + x^6 + x^3 + 1.
+pub fn sect163k1() -> NamedCurve<F2m<163>> {
+    curve(
+        "sect163k1",
+        &[7, 6, 3, 0],
+        (
+            "000000000000000000000000000000000000000001",
+            "000000000000000000000000000000000000000001",
+        ),
+        (
+            "02fe13c0537bbc11acaa07d793de4e6d5e5c94eee8",
+            "0289070fb05d38ff58321f2e800536d538ccdaa3d9",
+        ),
+        "04000000000000000000020108a2e0cc0d99f8a5ef",
+        2,
+    )
+}
+
+/// sect163r2 (NIST B-163), reduced by x^163 + x^7 + x^6 + x^3 + 1.
+pub fn sect163r2() -> NamedCurve<F2m<163>> {
+    curve(
+        "sect163r2",
+        &[7, 6, 3, 0],
+        (
+            "000000000000000000000000000000000000000001",
+            "020a601907b8c953ca1481eb10512f78744a3205fd",
+        ),
+        (
+            "03f0eba16286a2d57ea0991168d4994637e8343e36",
+            "00d51fbc6c71a0094fa2cdd545b11c5c0c797324f1",
+        ),
+        "040000000000000000000292fe77e70c12a4234c33",
+        2,
+    )
+}
+
+/// Koblitz curve sect233k1 (NIST K-233), reduced by x^233 + x^74 + 1.
+pub fn sect233k1() -> NamedCurve<F2m<233>> {
+    curve(
+        "sect233k1",
+        &[74, 0],
+        (
+            "000000000000000000000000000000000000000000000000000000000000",
+            "000000000000000000000000000000000000000000000000000000000001",
+        ),
+        (
+            "017232ba853a7e731af129f22ff4149563a419c26bf50a4c9d6eefad6126",
+            "01db537dece819b7f70f555a67c427a8cd9bf18aeb9b56e0c11056fae6a3",
+        ),
+        "8000000000000000000000000000069d5bb915bcd46efb1ad5f173abdf",
+        4,
+    )
+}
+
+/// sect233r1 (NIST B-233), reduced by x^233 + x^74 + 1.
+pub fn sect233r1() -> NamedCurve<F2m<233>> {
+    curve(
+        "sect233r1",
+        &[74, 0],
+        (
+            "000000000000000000000000000000000000000000000000000000000001",
+            "0066647ede6c332c7f8c0923bb58213b333b20e9ce4281fe115f7d8f90ad",
+        ),
+        (
+            "00fac9dfcbac8313bb2139f1bb755fef65bc391f8b36f8f8eb7371fd558b",
+            "01006a08a41903350678e58528bebf8a0beff867a7ca36716f7e01f81052",
+        ),
+        "01000000000000000000000000000013e974e72f8a6922031d2603cfe0d7",
+        2,
+    )
+}
+
+/// Koblitz curve sect283k1 (NIST K-283), reduced by x^283 + x^12 + x^7 + x^5 + 1.
+pub fn sect283k1() -> NamedCurve<F2m<283>> {
+    curve(
+        "sect283k1",
+        &[12, 7, 5, 0],
+        (
+            "000000000000000000000000000000000000000000000000000000000000000000000000",
+            "000000000000000000000000000000000000000000000000000000000000000000000001",
+        ),
+        (
+            "0503213f78ca44883f1a3b8162f188e553cd265f23c1567a16876913b0c2ac2458492836",
+            "01ccda380f1c9e318d90f95d07e5426fe87e45c0e8184698e45962364e34116177dd2259",
+        ),
+        "01ffffffffffffffffffffffffffffffffffe9ae2ed07577265dff7f94451e061e163c61",
+        4,
+    )
+}
@@ -0,0 +1,199 @@
+//! Catalogue of standard curves with verified base points, from SEC 2 and FIPS 186,
+//! and a few small curves for teaching and tests.
+
+use num_bigint::BigUint;
+
+use crate::{
+    elliptic_curve::{Ec, EcPoint},
+    fp_mont::{FpMont, MontgomeryParams},
+    traits::Field,
+};
+
+mod binary_curves;
+mod named_curve_impls;
+mod prime_curves;
+mod small_curves;
+
+pub use binary_curves::{sect113r1, sect163k1, sect163r2, sect233k1, sect233r1, sect283k1};
+pub use prime_curves::{p192, p224, p256, p384, p521, secp112r1, secp128r1, secp256k1};
+pub use small_curves::{small_f2_5, small_f17, small_f23};
+
+/// Curve with a base point of prime order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedCurve<F: Field> {
+    pub name: &'static str,
+    pub ec: Ec<F>,
+    /// Base point.
+    pub g: EcPoint<F>,
+    /// Prime order of `g`.
+    pub n: BigUint,
+    /// Cofactor, the curve has `h * n` points.
+    pub h: u32,
+}
+
+macro_rules! prime_field {
+    ($(#[$doc:meta])* $params:ident, $field:ident, $n:literal, $modulus:expr) => {
+        $(#[$doc])*
+        #[derive(Debug)]
+        pub struct $params;
+        impl MontgomeryParams<$n> for $params {
+            const MODULUS: [u64; $n] = $modulus;
+        }
+        pub type $field = FpMont<$params, $n>;
+    };
+}
+
+prime_field!(
+    /// p = 2^128 - 2^97 - 1
+    Secp128r1Prime,
+    Secp128r1Field,
+    2,
+    [0xffffffffffffffff, 0xfffffffdffffffff]
+);
+prime_field!(
+    /// p = 2^192 - 2^64 - 1
+    P192Prime,
+    P192Field,
+    3,
+    [0xffffffffffffffff, 0xfffffffffffffffe, 0xffffffffffffffff]
+);
+prime_field!(
+    /// p = 2^224 - 2^96 + 1
+    P224Prime,
+    P224Field,
+    4,
+    [
+        0x0000000000000001,
+        0xffffffff00000000,
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+    ]
+);
+prime_field!(
+    /// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+    P256Prime,
+    P256Field,
+    4,
+    [
+        0xffffffffffffffff,
+        0x00000000ffffffff,
+        0x0000000000000000,
+        0xffffffff00000001,
+    ]
+);
+prime_field!(
+    /// p = 2^256 - 2^32 - 977
+    Secp256k1Prime,
+    Secp256k1Field,
+    4,
+    [
+        0xfffffffefffffc2f,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]
+);
+prime_field!(
+    /// p = 2^384 - 2^128 - 2^96 + 2^32 - 1
+    P384Prime,
+    P384Field,
+    6,
+    [
+        0x00000000ffffffff,
+        0xffffffff00000000,
+        0xfffffffffffffffe,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+    ]
+);
+prime_field!(
+    /// p = 2^521 - 1
+    P521Prime,
+    P521Field,
+    9,
+    [
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0xffffffffffffffff,
+        0x00000000000001ff,
+    ]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{T, arithmetic::is_probable_prime, fp::Fp};
+
+    #[test]
+    fn prime_curves() {
+        assert!(secp112r1().self_check());
+        assert!(secp128r1().self_check());
+        assert!(secp256k1().self_check());
+        assert!(p192().self_check());
+        assert!(p224().self_check());
+        assert!(p256().self_check());
+        assert!(p384().self_check());
+        assert!(p521().self_check());
+    }
+
+    #[test]
+    fn binary_curves() {
+        assert!(sect113r1().self_check());
+        assert!(sect163k1().self_check());
+        assert!(sect163r2().self_check());
+        assert!(sect233k1().self_check());
+        assert!(sect233r1().self_check());
+        assert!(sect283k1().self_check());
+    }
+
+    #[test]
+    fn small_curves() {
+        let curve = small_f17();
+        assert!(curve.self_check());
+        assert!(small_f23().self_check());
+        assert!(small_f2_5().self_check());
+
+        // A wrong order or cofactor is caught
+        let mut wrong = curve.clone();
+        wrong.n += 1_u8;
+        assert!(!wrong.self_check());
+        let mut wrong = curve.clone();
+        wrong.h = 2;
+        assert!(!wrong.self_check());
+        let mut wrong = curve;
+        wrong.ec = Ec::new(wrong.ec.a, wrong.ec.b + wrong.ec.a);
+        assert!(!wrong.self_check());
+    }
+
+    #[test]
+    fn curve_orders() {
+        assert!(small_f17().is_order_correct());
+        assert!(small_f23().is_order_correct());
+        let mut wrong = small_f23();
+        wrong.h = 2;
+        assert!(!wrong.is_order_correct());
+
+        const Q: T = 1000003;
+        let curve = NamedCurve::generate("generated", Fp::<Q>::new(Q - 3), 1);
+        assert!(curve.self_check());
+        assert!(is_probable_prime(&curve.n));
+        assert_eq!(curve.ec.count_points_bsgs(), Some(curve.n));
+    }
+
+    #[test]
+    fn encoded_base_point() {
+        // Compressed generator of secp256k1 as published in SEC 2
+        let curve = secp256k1();
+        let compressed =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(curve.g.to_sec1(true), compressed);
+        assert_eq!(EcPoint::from_sec1(&compressed, &curve.ec), Ok(curve.g));
+    }
+}
@@ -0,0 +1,89 @@
+use std::ops::Neg;
+
+use num_bigint::BigUint;
+
+use super::NamedCurve;
+use crate::{
+    T,
+    arithmetic::is_probable_prime,
+    elliptic_curve::{Ec, EcPoint},
+    scalar::ScalarMul,
+    traits::{EcCalculations, FiniteField, Inverse, Normal, ProjectiveCalculations},
+};
+
+impl<F> NamedCurve<F>
+where
+    F: FiniteField,
+    Ec<F>: ProjectiveCalculations<F>,
+    EcPoint<F>: Neg<Output = EcPoint<F>>,
+{
+    /// Panics if the base point is not on the curve, the constants are meant to be checked.
+    pub(super) fn new(name: &'static str, ec: Ec<F>, (x, y): (F, F), n: &str, h: u32) -> Self {
+        let g = EcPoint::new(x, y, ec.clone()).expect("Base point must lie on the curve");
+        let n = BigUint::parse_bytes(n.as_bytes(), 16).expect("Order must be hexadecimal");
+        Self { name, ec, g, n, h }
+    }
+
+    /// Number of points of the curve.
+    pub fn order(&self) -> BigUint {
+        &self.n * self.h
+    }
+
+    /// Checks that the curve is non-singular, that `g` is a point of it other than
+    /// the point at infinity with n * g = O, and that the order `h * n` is within
+    /// the Hasse bound |q + 1 - h * n| <= 2 sqrt(q).
+    /// Primality of `n` is not checked.
+    pub fn self_check(&self) -> bool {
+        if self.ec.is_singular() || self.g.curve() != Some(&self.ec) || !self.g.is_on_curve() {
+            return false;
+        }
+        if !self.g.mul_vartime(&self.n).is_identity() {
+            return false;
+        }
+
+        let q_plus_one = self.ec.a.order() + 1_u8;
+        let order = self.order();
+        let distance = match q_plus_one > order {
+            true => q_plus_one - order,
+            false => order - q_plus_one,
+        };
+        distance.pow(2) <= self.ec.a.order() * 4_u8
+    }
+}
+
+impl<F> NamedCurve<F>
+where
+    F: FiniteField + Normal + Inverse,
+    Ec<F>: ProjectiveCalculations<F>,
+    EcPoint<F>: Neg<Output = EcPoint<F>>,
+{
+    /// Checks the order `h * n` against a count by Schoof's algorithm.
+    pub fn is_order_correct(&self) -> bool {
+        self.ec.count_points_schoof() == self.order()
+    }
+
+    /// First curve y^2 = x^3 + ax + b with a prime number of points other than q,
+    /// trying b = `a.element_at(i)` for i = `b_from`, `b_from + 1`, ...
+    /// The base point is the first point found by lifting x = 0, 1, ...
+    pub fn generate(name: &'static str, a: F, b_from: T) -> Self {
+        let q = a.order();
+        let (ec, n) = (b_from..)
+            .map(|i| Ec::new(a.clone(), a.element_at(i)))
+            .filter(|ec| !ec.is_singular())
+            .find_map(|ec| {
+                let n = ec.count_points_schoof();
+                (n != q && is_probable_prime(&n)).then_some((ec, n))
+            })
+            .unwrap();
+        let g = (0..)
+            .find_map(|i| ec.get_point_on_curve(a.element_at(i), false).ok())
+            .unwrap();
+        Self {
+            name,
+            ec,
+            g,
+            n,
+            h: 1,
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use super::{
+    NamedCurve, P192Field, P224Field, P256Field, P384Field, P521Field, Secp128r1Field,
+    Secp256k1Field,
+};
+use crate::{T, elliptic_curve::Ec, fp::Fp, fp_mont::FpMont, fp_mont::MontgomeryParams};
+
+/// Field element from big-endian hexadecimal.
+fn element<C: MontgomeryParams<N>, const N: usize>(hex: &str) -> FpMont<C, N> {
+    FpMont::from_be_bytes(&hex::decode(hex).expect("Constants must be hexadecimal"))
+}
+
+/// Curve with a = -3, as all the NIST curves.
+fn nist_curve<C: MontgomeryParams<N>, const N: usize>(b: &str) -> Ec<FpMont<C, N>> {
+    Ec::new(-FpMont::new(3), element(b))
+}
+
+const SECP112R1_P: T = 0xdb7c2abf62e35e668076bead208b;
+
+/// secp112r1, small enough for `Fp`. Too weak for anything but experiments.
+pub fn secp112r1() -> NamedCurve<Fp<SECP112R1_P>> {
+    NamedCurve::new(
+        "secp112r1",
+        Ec::new(
+            Fp::new(0xdb7c2abf62e35e668076bead2088),
+            Fp::new(0x659ef8ba043916eede8911702b22),
+        ),
+        (
+            Fp::new(0x09487239995a5ee76b55f9c2f098),
+            Fp::new(0xa89ce5af8724c0a23e0e0ff77500),
+        ),
+        "db7c2abf62e35e7628dfac6561c5",
+        1,
+    )
+}
+
+/// secp128r1. Too weak for anything but experiments.
+pub fn secp128r1() -> NamedCurve<Secp128r1Field> {
+    NamedCurve::new(
+        "secp128r1",
+        Ec::new(-FpMont::new(3), element("e87579c11079f43dd824993c2cee5ed3")),
+        (
+            element("161ff7528b899b2d0c28607ca52c5b86"),
+            element("cf5ac8395bafeb13c02da292dded7a83"),
+        ),
+        "fffffffe0000000075a30d1b9038a115",
+        1,
+    )
+}
+
+pub fn secp256k1() -> NamedCurve<Secp256k1Field> {
+    NamedCurve::new(
+        "secp256k1",
+        Ec::new(FpMont::new(0), FpMont::new(7)),
+        (
+            element("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            element("483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"),
+        ),
+        "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        1,
+    )
+}
+
+/// NIST P-192, also known as secp192r1.
+pub fn p192() -> NamedCurve<P192Field> {
+    NamedCurve::new(
+        "P-192",
+        nist_curve("64210519e59c80e70fa7e9ab72243049feb8deecc146b9b1"),
+        (
+            element("188da80eb03090f67cbf20eb43a18800f4ff0afd82ff1012"),
+            element("07192b95ffc8da78631011ed6b24cdd573f977a11e794811"),
+        ),
+        "ffffffffffffffffffffffff99def836146bc9b1b4d22831",
+        1,
+    )
+}
+
+/// NIST P-224, also known as secp224r1.
+pub fn p224() -> NamedCurve<P224Field> {
+    NamedCurve::new(
+        "P-224",
+        nist_curve("b4050a850c04b3abf54132565044b0b7d7bfd8ba270b39432355ffb4"),
+        (
+            element("b70e0cbd6bb4bf7f321390b94a03c1d356c21122343280d6115c1d21"),
+            element("bd376388b5f723fb4c22dfe6cd4375a05a07476444d5819985007e34"),
+        ),
+        "ffffffffffffffffffffffffffff16a2e0b8f03e13dd29455c5c2a3d",
+        1,
+    )
+}
+
+/// NIST P-256, also known as secp256r1.
+pub fn p256() -> NamedCurve<P256Field> {
+    NamedCurve::new(
+        "P-256",
+        nist_curve("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b"),
+        (
+            element("6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+            element("4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+        ),
+        "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+        1,
+    )
+}
+
+/// NIST P-384, also known as secp384r1.
+pub fn p384() -> NamedCurve<P384Field> {
+    NamedCurve::new(
+        "P-384",
+        nist_curve(
+            "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875a\
+             c656398d8a2ed19d2a85c8edd3ec2aef",
+        ),
+        (
+            element(
+                "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a38\
+                 5502f25dbf55296c3a545e3872760ab7",
+            ),
+            element(
+                "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c0\
+                 0a60b1ce1d7e819d7a431d7c90ea0e5f",
+            ),
+        ),
+        "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+         581a0db248b0a77aecec196accc52973",
+        1,
+    )
+}
+
+/// NIST P-521, also known as secp521r1.
+pub fn p521() -> NamedCurve<P521Field> {
+    NamedCurve::new(
+        "P-521",
+        nist_curve(
+            "0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef1\
+             09e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+        ),
+        (
+            element(
+                "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d\
+                 3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66",
+            ),
+            element(
+                "011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e\
+                 662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650",
+            ),
+        ),
+        "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+         fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+        1,
+    )
+}
@@ -0,0 +1,28 @@
+use super::NamedCurve;
+use crate::{
+    elliptic_curve::Ec,
+    f2m::{F2m, bit::Bits8},
+    fp::Fp,
+    polynomials::Polynomial,
+};
+
+/// y^2 = x^3 + 2x + 2 over F_17, a cyclic group of 19 points.
+pub fn small_f17() -> NamedCurve<Fp<17>> {
+    let ec = Ec::new(Fp::new(2), Fp::new(2));
+    NamedCurve::new("small-f17", ec, (Fp::new(5), Fp::new(1)), "13", 1)
+}
+
+/// y^2 = x^3 + x + 1 over F_23, 28 points with a base point of order 7.
+pub fn small_f23() -> NamedCurve<Fp<23>> {
+    let ec = Ec::new(Fp::new(1), Fp::new(1));
+    NamedCurve::new("small-f23", ec, (Fp::new(5), Fp::new(4)), "7", 4)
+}
+
+/// y^2 + xy = x^3 + x^2 + x over GF(2^5) reduced by x^5 + x^2 + 1,
+/// 34 points with a base point of order 17.
+pub fn small_f2_5() -> NamedCurve<F2m<5>> {
+    let modulo = Polynomial::new(vec![Bits8(0b100101)]);
+    let element = |bits: u8| F2m::new(Polynomial::new(vec![Bits8(bits)]), modulo.clone());
+    let ec = Ec::new(element(1), element(0b10));
+    NamedCurve::new("small-f2^5", ec, (element(1), element(0b1000)), "11", 2)
+}
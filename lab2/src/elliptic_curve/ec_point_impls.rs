@@ -1,15 +1,49 @@
-use std::ops::{Add, Mul, Neg, Sub};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, Deref, Mul, Neg, Sub};
+use std::sync::Arc;
 
-use super::{Ec, EcErrors, EcPoint, Field};
+use super::{Ec, EcErrors, EcParams, EcPoint, Field};
 use crate::{
     T,
     f2m::F2m,
-    traits::{EcCalculations, Inverse, Normal, Pow, Sqrt},
+    scalar::{ScalarBits, ScalarMul, montgomery_ladder},
+    traits::{
+        EcCalculations, FiniteField, Inverse, Normal, Parity, Pow, ProjectiveCalculations, Sqrt,
+    },
 };
 
+/// Returns n * 1 in the field of `like`.
+pub(super) fn from_int<F: Field>(like: &F, n: T) -> F {
+    (0..T::BITS - n.leading_zeros())
+        .rev()
+        .fold(like.zero(), |acc, i| {
+            let acc = acc.clone() + acc;
+            if (n >> i) & 1 == 1 {
+                acc + like.one()
+            } else {
+                acc
+            }
+        })
+}
+
+/// Absolute trace of x over the prime field of characteristic p.
+fn absolute_trace<F: FiniteField>(x: &F, p: T) -> F {
+    let mut q = x.order();
+    let mut term = x.clone();
+    let mut trace = x.zero();
+    while q > 1_u32.into() {
+        trace = trace + term.clone();
+        term = term.pow(p);
+        q /= p;
+    }
+    trace
+}
+
 impl<T: Field> Ec<T> {
     pub fn new(a: T, b: T) -> Self {
-        Self { a, b }
+        Self {
+            params: Arc::new(EcParams { a, b }),
+        }
     }
 
     pub fn get_infinity(&self) -> EcPoint<T> {
@@ -17,24 +51,82 @@ impl<T: Field> Ec<T> {
     }
 }
 
-impl<T: Field + Normal> EcCalculations<T> for Ec<T> {
-    fn get_point_on_curve(&self, x: T) -> Result<EcPoint<T>, EcErrors> {
-        let y = (x.clone().pow(3) + self.a.clone() * x.clone() + self.b.clone()); //.sqrt();
-        // match y {
-        //     Some(val) => Ok(EcPoint::Point {
-        //         x,
-        //         y: val,
-        //         ec: self.clone(),
-        //     }),
-        //     Option::None => Err(EcErrors::NoYValueForSpecifiedX),
-        // }
+impl<T: Field> Deref for Ec<T> {
+    type Target = EcParams<T>;
+
+    fn deref(&self) -> &EcParams<T> {
+        &self.params
+    }
+}
+
+/// Compares the parameters, by pointer first.
+impl<T: Field> PartialEq for Ec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.params, &other.params) || self.params == other.params
+    }
+}
+
+impl<T: Field> Eq for Ec<T> {}
+
+impl<T: Field> std::fmt::Debug for Ec<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ec")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .finish()
+    }
+}
+
+/// Hashes the coordinates only, points of different curves are unequal anyway.
+impl<T: Field + Hash> Hash for EcPoint<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let EcPoint::Point { x, y, .. } = self {
+            (x, y).hash(state);
+        }
+    }
+}
+
+impl<T> Ec<T>
+where
+    T: Field,
+    Ec<T>: EcCalculations<T>,
+{
+    /// Creates a curve, rejecting parameters for which it is singular.
+    pub fn try_new(a: T, b: T) -> Result<Self, EcErrors> {
+        let ec = Self::new(a, b);
+        match ec.is_singular() {
+            true => Err(EcErrors::SingularCurve),
+            false => Ok(ec),
+        }
+    }
+
+    pub fn is_singular(&self) -> bool {
+        let discriminant = self.discriminant();
+        discriminant == discriminant.zero()
+    }
+}
+
+impl<T> EcCalculations<T> for Ec<T>
+where
+    T: FiniteField + Normal + Sqrt + Parity + Inverse,
+{
+    fn get_point_on_curve(&self, x: T, y_bit: bool) -> Result<EcPoint<T>, EcErrors> {
+        let rhs = x.clone().pow(3) + self.a.clone() * x.clone() + self.b.clone();
+        let y = rhs.sqrt().ok_or(EcErrors::NoYValueForSpecifiedX)?;
+        let y = if y.is_odd() == y_bit { y } else { -y };
+
         Ok(EcPoint::Point {
             x,
-            y, //: val,
+            y,
             ec: self.clone(),
         })
     }
 
+    fn compression_bit(&self, _x: &T, y: &T) -> bool {
+        y.is_odd()
+    }
+
     #[inline]
     fn is_point_on_curve(&self, point: &EcPoint<T>) -> bool {
         match point {
@@ -46,7 +138,7 @@ impl<T: Field + Normal> EcCalculations<T> for Ec<T> {
     }
 
     fn add_points(&self, (x1, y1): (T, T), (x2, y2): (T, T)) -> EcPoint<T> {
-        let scale = (y1.clone() - y2) / (x1.clone() - x2.clone());
+        let scale = (y1.clone() - y2) * (x1.clone() - x2.clone()).inv();
         let x = scale.clone() * scale.clone() - x1.clone() - x2;
         let y = scale * (x1 - x.clone()) - y1;
         EcPoint::Point {
@@ -62,7 +154,8 @@ impl<T: Field + Normal> EcCalculations<T> for Ec<T> {
         }
 
         let x_sq = x.clone().pow(2);
-        let scale = (x_sq.clone() + x_sq.clone() + x_sq + self.a.clone()) / (y.clone() + y.clone());
+        let scale =
+            (x_sq.clone() + x_sq.clone() + x_sq + self.a.clone()) * (y.clone() + y.clone()).inv();
         let x_new = scale.clone() * scale.clone() - x.clone() - x.clone();
         let y_new = scale * (x.clone() - x_new.clone()) - y.clone();
         EcPoint::Point {
@@ -71,6 +164,79 @@ impl<T: Field + Normal> EcCalculations<T> for Ec<T> {
             ec: self.clone(),
         }
     }
+
+    /// -16 (4a^3 + 27b^2)
+    fn discriminant(&self) -> T {
+        let a = &self.a;
+        -from_int(a, 16)
+            * (from_int(a, 4) * a.clone().pow(3) + from_int(a, 27) * self.b.clone().pow(2))
+    }
+
+    /// 1728 * 4a^3 / (4a^3 + 27b^2)
+    fn j_invariant(&self) -> Option<T> {
+        if self.is_singular() {
+            return None;
+        }
+        let a = &self.a;
+        let a_cubed = from_int(a, 4) * a.clone().pow(3);
+        let denominator = a_cubed.clone() + from_int(a, 27) * self.b.clone().pow(2);
+        Some(from_int(a, 1728) * a_cubed * denominator.inv())
+    }
+
+    /// The only isomorphisms keeping the short form are x -> u^2 x + r, y -> u^3 y,
+    /// where r = 0 unless the characteristic is 3.
+    fn is_isomorphic(&self, other: &Self) -> bool {
+        if self.is_singular() || other.is_singular() {
+            return false;
+        }
+        let (a1, b1) = (self.a.clone(), self.b.clone());
+        let (a2, b2) = (other.a.clone(), other.b.clone());
+        let zero = a1.zero();
+
+        if from_int(&a1, 3) == zero {
+            // a2 = a1 / w^2 and b2 = (r^3 + a1 r + b1) / w^3 with w = u^2
+            let Some(s) = (a1.clone() * a2.inv()).sqrt() else {
+                return false;
+            };
+            return [s.clone(), -s].into_iter().any(|w| {
+                if !w.is_kth_power(2) {
+                    return false;
+                }
+                // r^3 + a1 r is linear over F_3, with r = t v it becomes t^3 (v^3 - v)
+                // whose image is the kernel of the trace
+                let c = b1.clone() - b2.clone() * w.pow(3);
+                match (-a1.clone()).sqrt() {
+                    None => true,
+                    Some(t) => absolute_trace(&(c * t.pow(3).inv()), 3) == zero,
+                }
+            });
+        }
+
+        match (a1 == zero, b1 == zero) {
+            // j = 0, b2 = u^6 b1
+            (true, _) => a2 == zero && (b2 * b1.inv()).is_kth_power(6),
+            // j = 1728, a2 = u^4 a1
+            (_, true) => b2 == zero && (a2 * a1.inv()).is_kth_power(4),
+            _ if a2 == zero || b2 == zero => false,
+            _ => {
+                let u_squared = b2.clone() * a1.clone() * (b1 * a2.clone()).inv();
+                u_squared.clone().pow(2) == a2 * a1.inv() && u_squared.is_kth_power(2)
+            }
+        }
+    }
+
+    /// y^2 = x^3 + a d^2 x + b d^3
+    fn quadratic_twist(&self, d: T) -> Ec<T> {
+        assert!(!d.is_kth_power(2), "Twist parameter must be a non-square");
+        Ec::new(self.a.clone() * d.clone().pow(2), self.b.clone() * d.pow(3))
+    }
+
+    fn twist_parameter(&self) -> T {
+        (2..)
+            .map(|i| self.a.element_at(i))
+            .find(|d| !d.is_kth_power(2))
+            .unwrap()
+    }
 }
 
 impl<const M: T> EcCalculations<F2m<M>> for Ec<F2m<M>> {
@@ -84,8 +250,45 @@ impl<const M: T> EcCalculations<F2m<M>> for Ec<F2m<M>> {
         }
     }
 
-    fn get_point_on_curve(&self, _x: F2m<M>) -> Result<EcPoint<F2m<M>>, EcErrors> {
-        todo!()
+    fn get_point_on_curve(&self, x: F2m<M>, y_bit: bool) -> Result<EcPoint<F2m<M>>, EcErrors> {
+        if x.is_zero() {
+            return Ok(EcPoint::Point {
+                x,
+                y: self
+                    .b
+                    .clone()
+                    .sqrt()
+                    .expect("Every element of F2m is a square"),
+                ec: self.clone(),
+            });
+        }
+
+        // Substituting y = xz gives z^2 + z = x + a + b / x^2
+        let x_inv = x.clone().inv();
+        let beta = x.clone() + self.a.clone() + self.b.clone() * x_inv.clone() * x_inv;
+        let z = beta
+            .solve_quadratic()
+            .ok_or(EcErrors::NoYValueForSpecifiedX)?;
+        let z_bit = z.coefficients().first().is_some_and(|c| c.0 & 1 == 1);
+        let z = if z_bit == y_bit {
+            z
+        } else {
+            z.clone() + z.one()
+        };
+
+        Ok(EcPoint::Point {
+            y: x.clone() * z,
+            x,
+            ec: self.clone(),
+        })
+    }
+
+    fn compression_bit(&self, x: &F2m<M>, y: &F2m<M>) -> bool {
+        if x.is_zero() {
+            return false;
+        }
+        let z = y.clone() * x.clone().inv();
+        z.coefficients().first().is_some_and(|c| c.0 & 1 == 1)
     }
 
     fn add_points(
@@ -93,7 +296,7 @@ impl<const M: T> EcCalculations<F2m<M>> for Ec<F2m<M>> {
         (x1, y1): (F2m<M>, F2m<M>),
         (x2, y2): (F2m<M>, F2m<M>),
     ) -> EcPoint<F2m<M>> {
-        let scale = (y1.clone() + y2) / (x1.clone() - x2.clone());
+        let scale = (y1.clone() + y2) * (x1.clone() - x2.clone()).inv();
         let x = scale.clone().pow(2) + scale.clone() + self.a.clone() + x1.clone() + x2;
         let y = scale * (x1 + x.clone()) - x.clone() + y1;
         EcPoint::Point {
@@ -108,7 +311,7 @@ impl<const M: T> EcCalculations<F2m<M>> for Ec<F2m<M>> {
             return EcPoint::Infinity;
         }
 
-        let scale = x.clone() + y.clone() / x.clone();
+        let scale = x.clone() + y.clone() * x.clone().inv();
         let x_new = scale.clone().pow(2) + scale.clone() - self.a.clone();
         let y_new = scale * (x.clone() + x_new.clone()) + x_new.clone() + y.clone();
         EcPoint::Point {
@@ -117,6 +320,33 @@ impl<const M: T> EcCalculations<F2m<M>> for Ec<F2m<M>> {
             ec: self.clone(),
         }
     }
+
+    fn discriminant(&self) -> F2m<M> {
+        self.b.clone()
+    }
+
+    fn j_invariant(&self) -> Option<F2m<M>> {
+        (!self.is_singular()).then(|| self.b.clone().inv())
+    }
+
+    /// The only isomorphisms keeping the form y^2 + xy = x^3 + ax^2 + b are y -> y + sx,
+    /// which replace a with a + s^2 + s and keep b.
+    fn is_isomorphic(&self, other: &Self) -> bool {
+        !self.is_singular() && self.b == other.b && self.a.trace() == other.a.trace()
+    }
+
+    /// y^2 + xy = x^3 + (a + d) x^2 + b
+    fn quadratic_twist(&self, d: F2m<M>) -> Ec<F2m<M>> {
+        assert!(d.trace(), "Twist parameter must have trace one");
+        Ec::new(self.a.clone() + d, self.b.clone())
+    }
+
+    fn twist_parameter(&self) -> F2m<M> {
+        (1..)
+            .map(|i| self.a.element_at(i))
+            .find(F2m::trace)
+            .unwrap()
+    }
 }
 
 impl<T> EcPoint<T>
@@ -124,19 +354,33 @@ where
     T: Field,
     Ec<T>: EcCalculations<T>,
 {
+    /// Creates a point, rejecting coordinates that do not satisfy the curve equation.
     pub fn new(x: T, y: T, ec: Ec<T>) -> Result<Self, EcErrors> {
         let point = Self::Point { x, y, ec };
         match point.is_on_curve() {
-            _ => Ok(point),
-            // true => Ok(point),
-            // false => Err(EcErrors::PointNotOnCurve),
+            true => Ok(point),
+            false => Err(EcErrors::PointNotOnCurve),
         }
     }
 
+    /// Creates a point without checking the curve equation.
+    /// Only for coordinates that are already known to be valid.
+    pub fn new_unchecked(x: T, y: T, ec: Ec<T>) -> Self {
+        Self::Point { x, y, ec }
+    }
+
     pub fn infinity() -> Self {
         Self::Infinity
     }
 
+    /// Curve of the point, `None` for the point at infinity.
+    pub fn curve(&self) -> Option<&Ec<T>> {
+        match self {
+            EcPoint::Point { ec, .. } => Some(ec),
+            EcPoint::Infinity => None,
+        }
+    }
+
     #[inline]
     pub fn match_ec(lhs: &Self, rhs: &Self) -> bool {
         match (lhs, rhs) {
@@ -212,9 +456,23 @@ where
     type Output = Self;
 
     fn add(self, other: EcPoint<T>) -> Self::Output {
-        assert!(Self::match_ec(&self, &other), "Not compatible Ec");
+        self.checked_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T> EcPoint<T>
+where
+    T: Field,
+    Ec<T>: EcCalculations<T>,
+    EcPoint<T>: Neg<Output = Self>,
+{
+    /// Adds two points, failing with `CurveMismatch` if they lie on different curves.
+    pub fn checked_add(self, other: Self) -> Result<Self, EcErrors> {
+        if !Self::match_ec(&self, &other) {
+            return Err(EcErrors::CurveMismatch);
+        }
 
-        match (self, other) {
+        Ok(match (self, other) {
             (EcPoint::Infinity, p) => p,
             (p, EcPoint::Infinity) => p,
             (p1, p2) if p1 == p2.clone().neg() => EcPoint::Infinity,
@@ -227,7 +485,7 @@ where
                     ec: _,
                 },
             ) => ec.add_points((x1, y1), (x2, y2)),
-        }
+        })
     }
 }
 
@@ -258,6 +516,7 @@ where
 impl<T> Pow for EcPoint<T>
 where
     T: Field,
+    Ec<T>: ProjectiveCalculations<T>,
     EcPoint<T>: Add<Output = Self>,
 {
     fn zero(&self) -> Self {
@@ -266,12 +525,86 @@ where
     fn one(&self) -> Self {
         Self::Infinity
     }
+
+    /// Double-and-add in projective coordinates, inverting only once at the end.
+    fn pow(self, exp: u128) -> Self {
+        let EcPoint::Point { x, y, ec } = &self else {
+            return Self::Infinity;
+        };
+
+        let mut result = ec.to_projective(&Self::Infinity);
+        for i in (0..u128::BITS - exp.leading_zeros()).rev() {
+            result = ec.double_projective(&result);
+            if (exp >> i) & 1 == 1 {
+                result = ec.add_mixed(&result, (x, y));
+            }
+        }
+        ec.to_affine(&result)
+    }
+}
+
+/// Works in projective coordinates, inverting only once at the end.
+impl<T> ScalarMul for EcPoint<T>
+where
+    T: Field,
+    Ec<T>: ProjectiveCalculations<T>,
+    EcPoint<T>: Neg<Output = EcPoint<T>>,
+{
+    type Repr = <Ec<T> as ProjectiveCalculations<T>>::Point;
+
+    fn is_identity(&self) -> bool {
+        *self == Self::Infinity
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        self.projective_curve().to_projective(self)
+    }
+
+    fn normalize(&self, repr: &Self::Repr) -> Self {
+        self.projective_curve().to_affine(repr)
+    }
+
+    fn identity_repr(&self) -> Self::Repr {
+        self.projective_curve().to_projective(&Self::Infinity)
+    }
+
+    fn add_repr(&self, a: &Self::Repr, b: &Self::Repr) -> Self::Repr {
+        self.projective_curve().add_projective(a, b)
+    }
+
+    fn double_repr(&self, a: &Self::Repr) -> Self::Repr {
+        self.projective_curve().double_projective(a)
+    }
+
+    fn negate(&self) -> Self {
+        -self.clone()
+    }
+
+    /// The point at infinity does not know its curve, so it cannot go through the
+    /// ladder. That only tells on the base, never on the scalar.
+    fn ladder<S: ScalarBits + ?Sized>(&self, scalar: &S) -> Self {
+        match self {
+            Self::Infinity => Self::Infinity,
+            _ => montgomery_ladder(self, scalar),
+        }
+    }
+}
+
+impl<T> EcPoint<T>
+where
+    T: Field,
+    Ec<T>: ProjectiveCalculations<T>,
+{
+    fn projective_curve(&self) -> &Ec<T> {
+        self.curve()
+            .expect("The point at infinity does not know its curve")
+    }
 }
 
 impl<T> Mul<usize> for EcPoint<T>
 where
     T: Field,
-    EcPoint<T>: Add<Output = Self>,
+    EcPoint<T>: Pow,
 {
     type Output = Self;
 
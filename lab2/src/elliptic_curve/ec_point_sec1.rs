@@ -0,0 +1,57 @@
+use super::{Ec, EcErrors, EcPoint};
+use crate::traits::{EcCalculations, FieldBytes};
+
+/// Encoding of SEC1 section 2.3.3 and 2.3.4.
+impl<T> EcPoint<T>
+where
+    T: FieldBytes,
+    Ec<T>: EcCalculations<T>,
+{
+    /// `04 || x || y`, or `02 || x` / `03 || x` when compressed, where the low bit of
+    /// the tag is the compression bit of the curve. Infinity is the single byte `00`.
+    pub fn to_sec1(&self, compressed: bool) -> Vec<u8> {
+        let EcPoint::Point { x, y, ec } = self else {
+            return vec![0];
+        };
+
+        let mut bytes = match compressed {
+            true => vec![2 | ec.compression_bit(x, y) as u8],
+            false => vec![4],
+        };
+        bytes.extend(x.to_fixed_bytes());
+        if !compressed {
+            bytes.extend(y.to_fixed_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a point of `ec`, recovering y of a compressed point from the curve equation.
+    /// Fails unless the result is a point of the curve.
+    pub fn from_sec1(bytes: &[u8], ec: &Ec<T>) -> Result<Self, EcErrors> {
+        let len = ec.a.byte_len();
+        let parse = |bytes: &[u8]| {
+            ec.a.parse_fixed_bytes(bytes)
+                .ok_or(EcErrors::InvalidEncoding)
+        };
+
+        match bytes.split_first() {
+            Some((0, [])) => Ok(EcPoint::Infinity),
+            Some((&tag @ (2 | 3), x)) if x.len() == len => {
+                let y_bit = tag == 3;
+                let point = ec.get_point_on_curve(parse(x)?, y_bit)?;
+                // For y = 0 both bits lead to the same point, only one of them is valid
+                match &point {
+                    EcPoint::Point { x, y, .. } if ec.compression_bit(x, y) != y_bit => {
+                        Err(EcErrors::InvalidEncoding)
+                    }
+                    _ => Ok(point),
+                }
+            }
+            Some((4, coordinates)) if coordinates.len() == 2 * len => {
+                let (x, y) = coordinates.split_at(len);
+                EcPoint::new(parse(x)?, parse(y)?, ec.clone())
+            }
+            _ => Err(EcErrors::InvalidEncoding),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use super::EcPoint;
-use crate::traits::Field;
+use super::{Ec, EcPoint};
+use crate::traits::{EcCalculations, Field};
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
     de::{MapAccess, Visitor},
@@ -25,14 +25,22 @@ impl<T: Field + Serialize> Serialize for EcPoint<T> {
     }
 }
 
-impl<'de, T: Field + Deserialize<'de>> Deserialize<'de> for EcPoint<T> {
+impl<'de, T> Deserialize<'de> for EcPoint<T>
+where
+    T: Field + Deserialize<'de>,
+    Ec<T>: EcCalculations<T>,
+{
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         struct EcPointVisitor<T>(std::marker::PhantomData<T>);
 
-        impl<'de, T: Field + Deserialize<'de>> Visitor<'de> for EcPointVisitor<T> {
+        impl<'de, T> Visitor<'de> for EcPointVisitor<T>
+        where
+            T: Field + Deserialize<'de>,
+            Ec<T>: EcCalculations<T>,
+        {
             type Value = EcPoint<T>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -75,7 +83,7 @@ impl<'de, T: Field + Deserialize<'de>> Deserialize<'de> for EcPoint<T> {
                 let y = y.ok_or_else(|| serde::de::Error::missing_field("y"))?;
                 let ec = ec.ok_or_else(|| serde::de::Error::missing_field("ec"))?;
 
-                Ok(EcPoint::Point { x, y, ec })
+                EcPoint::new(x, y, ec).map_err(serde::de::Error::custom)
             }
         }
 
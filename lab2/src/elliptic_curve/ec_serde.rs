@@ -67,7 +67,7 @@ impl<'de, T: Field + Deserialize<'de>> Deserialize<'de> for Ec<T> {
                 let a = a.ok_or_else(|| serde::de::Error::missing_field("a"))?;
                 let b = b.ok_or_else(|| serde::de::Error::missing_field("b"))?;
 
-                Ok(Ec { a, b })
+                Ok(Ec::new(a, b))
             }
         }
 
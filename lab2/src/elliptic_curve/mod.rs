@@ -1,13 +1,28 @@
+use std::sync::Arc;
+
 use super::traits::Field;
+use crate::{T, f2m::F2m};
 
 mod ec_point_impls;
+mod ec_point_sec1;
 mod ec_point_serde;
 mod ec_serde;
+mod order_impls;
+mod projective_impls;
+mod schoof_impls;
+mod weierstrass_impls;
 
 /// Elliptic curve over a finite field.
 /// Described by the equation y^2 = x^3 + ax + b.
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Clones share the parameters, so a point only holds a pointer to its curve.
+#[derive(Clone)]
 pub struct Ec<T: Field> {
+    params: Arc<EcParams<T>>,
+}
+
+/// Parameters of an `Ec`, which it dereferences to.
+#[derive(Debug, PartialEq, Eq)]
+pub struct EcParams<T: Field> {
     pub a: T,
     pub b: T,
 }
@@ -19,16 +34,88 @@ pub enum EcPoint<T: Field> {
     Infinity,
 }
 
-#[derive(Debug)]
+/// Point on a short Weierstrass curve in Jacobian coordinates,
+/// (X : Y : Z) stands for the affine point (X / Z^2, Y / Z^3) and Z = 0 for infinity.
+#[derive(Debug, Clone)]
+pub struct JacobianPoint<T: Field> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// Point on a binary curve in López–Dahab coordinates,
+/// (X : Y : Z) stands for the affine point (X / Z, Y / Z^2) and Z = 0 for infinity.
+#[derive(Debug, Clone)]
+pub struct LopezDahabPoint<const M: T> {
+    pub x: F2m<M>,
+    pub y: F2m<M>,
+    pub z: F2m<M>,
+}
+
+/// Elliptic curve in the general Weierstrass form
+/// y^2 + a1 xy + a3 y = x^3 + a2 x^2 + a4 x + a6.
+/// Unlike `Ec` it can describe curves over any field, including supersingular binary curves.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WeierstrassEc<T: Field> {
+    pub a1: T,
+    pub a2: T,
+    pub a3: T,
+    pub a4: T,
+    pub a6: T,
+}
+
+/// Point on a general Weierstrass curve.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WeierstrassPoint<T: Field> {
+    Point { x: T, y: T, ec: WeierstrassEc<T> },
+    Infinity,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum EcErrors {
+    /// The coordinates do not satisfy the curve equation.
     PointNotOnCurve,
+    /// The curve has no point with the requested x coordinate.
     NoYValueForSpecifiedX,
+    /// The points lie on different curves.
+    CurveMismatch,
+    /// The curve parameters give a zero discriminant.
+    SingularCurve,
+    /// The bytes are not a valid encoding of a point.
+    InvalidEncoding,
+}
+
+impl std::fmt::Display for EcErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            EcErrors::PointNotOnCurve => "point is not on the curve",
+            EcErrors::NoYValueForSpecifiedX => "no point on the curve has the given x coordinate",
+            EcErrors::CurveMismatch => "points lie on different curves",
+            EcErrors::SingularCurve => "curve is singular",
+            EcErrors::InvalidEncoding => "invalid point encoding",
+        };
+        write!(f, "{}", message)
+    }
 }
 
+impl std::error::Error for EcErrors {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{T, fp::Fp};
+    use crate::{
+        T,
+        f2m::{F2m, bit::Bits8},
+        fp::Fp,
+        fpk::Fpk,
+        polynomials::Polynomial,
+        traits::{
+            EcCalculations, FieldBytes, FiniteField, Inverse, Normal, Parity, Pow,
+            ProjectiveCalculations, Sqrt,
+        },
+    };
+    use num_bigint::BigUint;
+    use std::ops::Add;
     const P: T = 19;
 
     #[test]
@@ -36,7 +123,7 @@ mod tests {
         let p1: Fp<P> = Fp::new(5);
         let p2: Fp<P> = Fp::new(10);
         let ec = Ec::new(p1, p2);
-        let point = EcPoint::new(p1, p2, ec).expect("Point on curve");
+        let point = EcPoint::new_unchecked(p1, p2, ec);
         let inf: EcPoint<Fp<P>> = EcPoint::infinity();
 
         assert_eq!(inf, inf);
@@ -51,9 +138,9 @@ mod tests {
         let p2: Fp<P> = Fp::new(10);
         let p3: Fp<P> = Fp::new(9);
         let ec = Ec::new(p1, p2);
-        let point1 = EcPoint::new(p1, p2, ec.clone()).expect("Point on curve");
-        let point2 = EcPoint::new(p2, p1, ec.clone()).expect("Point on curve");
-        let point_ecpected = EcPoint::new(p1, p3, ec).expect("Point on curve");
+        let point1 = EcPoint::new_unchecked(p1, p2, ec.clone());
+        let point2 = EcPoint::new_unchecked(p2, p1, ec.clone());
+        let point_ecpected = EcPoint::new_unchecked(p1, p3, ec);
         let inf: EcPoint<Fp<P>> = EcPoint::infinity();
 
         // assert!(point1.is_on_curve());
@@ -64,4 +151,733 @@ mod tests {
         assert_eq!(point1.clone() + point1.clone(), point1.double());
         assert_eq!(point1.clone() + point2.clone(), point_ecpected);
     }
+
+    #[test]
+    fn point_validation() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+
+        assert!(EcPoint::new(Fp::new(5), Fp::new(1), ec.clone()).is_ok());
+        assert_eq!(
+            EcPoint::new(Fp::new(5), Fp::new(2), ec),
+            Err(EcErrors::PointNotOnCurve)
+        );
+    }
+
+    #[test]
+    fn deserialization_rejects_invalid_points() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let valid =
+            serde_json::to_string(&EcPoint::new_unchecked(Fp::new(5), Fp::new(1), ec.clone()));
+        let invalid = serde_json::to_string(&EcPoint::new_unchecked(Fp::new(5), Fp::new(2), ec));
+
+        assert!(serde_json::from_str::<EcPoint<Fp<Q>>>(&valid.unwrap()).is_ok());
+        assert!(serde_json::from_str::<EcPoint<Fp<Q>>>(&invalid.unwrap()).is_err());
+    }
+
+    #[test]
+    fn addition_on_different_curves() {
+        const Q: T = 17;
+        let p1 = EcPoint::new(Fp::<Q>::new(5), Fp::new(1), Ec::new(Fp::new(2), Fp::new(2)));
+        let p2 = EcPoint::new(Fp::<Q>::new(0), Fp::new(1), Ec::new(Fp::new(1), Fp::new(1)));
+        let (p1, p2) = (p1.unwrap(), p2.unwrap());
+
+        assert_eq!(p1.clone().checked_add(p2), Err(EcErrors::CurveMismatch));
+        assert_eq!(p1.clone().checked_add(EcPoint::Infinity), Ok(p1));
+    }
+
+    #[test]
+    fn points_share_their_curve() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let p = EcPoint::new(Fp::new(5), Fp::new(1), ec.clone()).unwrap();
+        let shares_curve =
+            |point: &EcPoint<Fp<Q>>| Arc::ptr_eq(&point.curve().unwrap().params, &ec.params);
+
+        assert!(shares_curve(&p.double()));
+        assert!(shares_curve(&(p.clone() + p.double())));
+        assert!(shares_curve(&-p.clone()));
+        assert!(shares_curve(&p.clone().pow(5)));
+
+        // Curves created separately are compared by their parameters
+        let other = EcPoint::new(Fp::new(5), Fp::new(1), Ec::new(Fp::new(2), Fp::new(2)));
+        assert_eq!(p.clone() + other.unwrap(), p.double());
+        assert_ne!(ec, Ec::new(Fp::new(1), Fp::new(1)));
+    }
+
+    #[test]
+    fn get_point_on_prime_curve() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        for i in 0..Q {
+            let x: Fp<Q> = Fp::new(i);
+            let lifts = (0..Q).filter(|&j| {
+                ec.is_point_on_curve(&EcPoint::Point {
+                    x,
+                    y: Fp::new(j),
+                    ec: ec.clone(),
+                })
+            });
+
+            match (
+                ec.get_point_on_curve(x, false),
+                ec.get_point_on_curve(x, true),
+            ) {
+                (Ok(even), Ok(odd)) => {
+                    assert!(even.is_on_curve() && odd.is_on_curve());
+                    assert_eq!(-even.clone(), odd);
+                    if let EcPoint::Point { y, .. } = even {
+                        assert!(!y.is_odd());
+                    }
+                }
+                (Err(EcErrors::NoYValueForSpecifiedX), Err(_)) => assert_eq!(lifts.count(), 0),
+                _ => panic!("Inconsistent lifts of {}", x),
+            }
+        }
+    }
+
+    fn check_binary_lifts<const M: T>(modulo: Bits8) {
+        let modulo = Polynomial::new(vec![modulo]);
+        let element = |i: u8| F2m::<M>::new(Polynomial::new(vec![Bits8(i)]), modulo.clone());
+        let ec = Ec::new(element(1), element(0b11));
+
+        for i in 0..1 << M {
+            let x = element(i);
+            let lifts = (0..1 << M).filter(|&j| {
+                ec.is_point_on_curve(&EcPoint::Point {
+                    x: x.clone(),
+                    y: element(j),
+                    ec: ec.clone(),
+                })
+            });
+
+            match (
+                ec.get_point_on_curve(x.clone(), false),
+                ec.get_point_on_curve(x.clone(), true),
+            ) {
+                (Ok(p0), Ok(p1)) => {
+                    assert!(p0.is_on_curve() && p1.is_on_curve());
+                    assert_eq!(lifts.count(), if i == 0 { 1 } else { 2 });
+                    if i != 0 {
+                        assert_eq!(-p0.clone(), p1);
+                        if let EcPoint::Point { y, .. } = p1 {
+                            let z = y * x.inv();
+                            assert_eq!(z.coefficients()[0].0 & 1, 1);
+                        }
+                    }
+                }
+                (Err(EcErrors::NoYValueForSpecifiedX), Err(_)) => assert_eq!(lifts.count(), 0),
+                _ => panic!("Inconsistent lifts of {}", x),
+            }
+        }
+    }
+
+    #[test]
+    fn get_point_on_binary_curve() {
+        // Odd M uses the half-trace, even M the general quadratic solver
+        check_binary_lifts::<5>(Bits8(0b100101));
+        check_binary_lifts::<4>(Bits8(0b10011));
+        check_binary_lifts::<3>(Bits8(0b1011));
+    }
+
+    fn ec_points<F: Field>(ec: &Ec<F>, elements: &[F]) -> Vec<EcPoint<F>>
+    where
+        Ec<F>: EcCalculations<F>,
+    {
+        let affine = elements.iter().flat_map(|x| {
+            elements
+                .iter()
+                .map(|y| EcPoint::new_unchecked(x.clone(), y.clone(), ec.clone()))
+        });
+        affine
+            .filter(|p| p.is_on_curve())
+            .chain([EcPoint::Infinity])
+            .collect()
+    }
+
+    fn count_points<F: Field>(ec: &Ec<F>, elements: &[F]) -> usize
+    where
+        Ec<F>: EcCalculations<F>,
+    {
+        ec_points(ec, elements).len()
+    }
+
+    /// Compares with a search over all substitutions x -> u^2 x + r, y -> u^3 y.
+    fn check_short_weierstrass<F>(elements: Vec<F>)
+    where
+        F: FiniteField + Normal + Sqrt + Parity + Inverse,
+    {
+        let zero = elements[0].zero();
+        let three = zero.one() + zero.one() + zero.one();
+        let shifts: Vec<F> = match three == zero {
+            true => elements.clone(),
+            false => vec![zero.clone()],
+        };
+        let curves: Vec<Ec<F>> = elements
+            .iter()
+            .flat_map(|a| elements.iter().map(|b| Ec::new(a.clone(), b.clone())))
+            .collect();
+
+        for e1 in &curves {
+            let (a, b) = (e1.a.clone(), e1.b.clone());
+            let repeated_root = elements.iter().any(|x| {
+                x.clone().pow(3) + a.clone() * x.clone() + b.clone() == zero
+                    && three.clone() * x.clone().pow(2) + a.clone() == zero
+            });
+            assert_eq!(e1.is_singular(), repeated_root, "{:?}", e1);
+            assert_eq!(Ec::try_new(a.clone(), b.clone()).is_err(), repeated_root);
+            if repeated_root {
+                continue;
+            }
+
+            let images: Vec<Ec<F>> = elements
+                .iter()
+                .filter(|u| **u != zero)
+                .flat_map(|u| {
+                    let (a, b) = (&a, &b);
+                    let (u4, u6) = (u.clone().pow(4).inv(), u.clone().pow(6).inv());
+                    shifts.iter().map(move |r| {
+                        let b2 = r.clone().pow(3) + a.clone() * r.clone() + b.clone();
+                        Ec::new(a.clone() * u4.clone(), b2 * u6.clone())
+                    })
+                })
+                .collect();
+            for e2 in &curves {
+                let isomorphic = images.contains(e2);
+                assert_eq!(e1.is_isomorphic(e2), isomorphic, "{:?} {:?}", e1, e2);
+                if isomorphic {
+                    assert_eq!(e1.j_invariant(), e2.j_invariant());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn short_weierstrass_invariants() {
+        check_short_weierstrass((0..7).map(Fp::<7>::new).collect());
+        check_short_weierstrass((0..13).map(Fp::<13>::new).collect());
+
+        let modulo: Polynomial<Fp<3>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let elements = (0..9)
+            .map(|i| Fpk::<3, 2>::new(Polynomial::new_from_slice(&[i % 3, i / 3]), modulo.clone()))
+            .collect();
+        check_short_weierstrass(elements);
+    }
+
+    #[test]
+    fn j_invariant_and_twist() {
+        const Q: T = 13;
+        let elements: Vec<Fp<Q>> = (0..Q).map(Fp::new).collect();
+        let ec = Ec::try_new(Fp::<Q>::new(2), Fp::new(3)).unwrap();
+        let twist = ec.quadratic_twist(Fp::new(2));
+
+        assert_eq!(
+            Ec::new(Fp::<Q>::new(0), Fp::new(1)).j_invariant(),
+            Some(Fp::new(0))
+        );
+        assert_eq!(
+            Ec::new(Fp::<Q>::new(1), Fp::new(0)).j_invariant(),
+            Some(Fp::new(1728))
+        );
+        assert_eq!(Ec::new(Fp::<Q>::new(0), Fp::new(0)).j_invariant(), None);
+        assert_eq!(ec.j_invariant(), twist.j_invariant());
+        assert!(!ec.is_isomorphic(&twist));
+        assert_eq!(
+            count_points(&ec, &elements) + count_points(&twist, &elements),
+            2 * Q as usize + 2
+        );
+    }
+
+    #[test]
+    fn binary_invariants() {
+        const M: T = 3;
+        let modulo = Polynomial::new(vec![Bits8(0b1011)]);
+        let elements: Vec<F2m<M>> = (0..8)
+            .map(|i| F2m::new(Polynomial::new(vec![Bits8(i)]), modulo.clone()))
+            .collect();
+        // a = 0, 1 already cover both traces
+        let curves: Vec<Ec<F2m<M>>> = elements[..4]
+            .iter()
+            .flat_map(|a| elements.iter().map(|b| Ec::new(a.clone(), b.clone())))
+            .collect();
+        let counts: Vec<usize> = curves
+            .iter()
+            .map(|ec| count_points(ec, &elements))
+            .collect();
+
+        for (e1, count1) in curves.iter().zip(&counts) {
+            assert_eq!(e1.is_singular(), e1.b == elements[0]);
+            if e1.is_singular() {
+                assert_eq!(
+                    Ec::try_new(e1.a.clone(), e1.b.clone()),
+                    Err(EcErrors::SingularCurve)
+                );
+                continue;
+            }
+            assert_eq!(
+                e1.j_invariant().map(|j| j * e1.b.clone()),
+                Some(elements[1].clone())
+            );
+
+            // Ordinary curves with the same j-invariant are isomorphic or twists,
+            // and twists have different numbers of points
+            let twist = e1.quadratic_twist(elements[1].clone());
+            assert!(!e1.is_isomorphic(&twist));
+            assert_eq!(count1 + count_points(&twist, &elements), 18);
+            for (e2, count2) in curves.iter().zip(&counts) {
+                let isomorphic = e1.b == e2.b && count1 == count2;
+                assert_eq!(e1.is_isomorphic(e2), isomorphic);
+            }
+        }
+    }
+
+    fn weierstrass_points<F: Field + Inverse>(
+        ec: &WeierstrassEc<F>,
+        elements: &[F],
+    ) -> Vec<WeierstrassPoint<F>> {
+        let affine = elements.iter().flat_map(|x| {
+            elements
+                .iter()
+                .map(|y| WeierstrassPoint::new_unchecked(x.clone(), y.clone(), ec.clone()))
+        });
+        affine
+            .filter(|p| p.is_on_curve())
+            .chain([WeierstrassPoint::Infinity])
+            .collect()
+    }
+
+    /// Closure, inverses, associativity and Lagrange's theorem on the whole group.
+    fn check_group<F: Field + Inverse>(points: &[WeierstrassPoint<F>]) {
+        let order = points.len();
+        for p in points {
+            assert_eq!(p.clone() + (-p.clone()), WeierstrassPoint::Infinity);
+            assert_eq!(p.clone() * order, WeierstrassPoint::Infinity);
+            for q in points {
+                let sum = p.clone() + q.clone();
+                assert!(points.contains(&sum));
+                assert_eq!(sum, q.clone() + p.clone());
+                for r in points.iter().step_by(3) {
+                    assert_eq!(sum.clone() + r.clone(), p.clone() + (q.clone() + r.clone()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn weierstrass_to_short_form() {
+        const Q: T = 19;
+        let elements: Vec<Fp<Q>> = (0..Q).map(Fp::new).collect();
+        let [a1, a2, a3, a4, a6] = [1, 2, 3, 4, 5].map(Fp::<Q>::new);
+        let ec = WeierstrassEc::try_new(a1, a2, a3, a4, a6).unwrap();
+        let short = ec.to_short().unwrap();
+        let points = weierstrass_points(&ec, &elements);
+
+        assert_eq!(ec.j_invariant(), short.j_invariant());
+        check_group(&points);
+        for p in &points {
+            let image = p.to_short().unwrap();
+            assert!(image.is_on_curve());
+            assert!(image == EcPoint::Infinity || image.curve() == Some(&short));
+            for q in &points {
+                let sum = p.clone() + q.clone();
+                assert_eq!(
+                    sum.to_short().unwrap(),
+                    image.clone() + q.to_short().unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn weierstrass_from_short_form() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let p = EcPoint::new(Fp::new(5), Fp::new(1), ec.clone()).unwrap();
+        let q = EcPoint::new(Fp::new(0), Fp::new(6), ec.clone()).unwrap();
+        let general = WeierstrassEc::from(ec.clone());
+
+        assert_eq!(general.j_invariant(), ec.j_invariant());
+        assert_eq!(general.discriminant(), ec.discriminant());
+        for i in 1..20 {
+            let sum = p.clone() * i + q.clone();
+            let general_sum =
+                WeierstrassPoint::from(p.clone()) * i + WeierstrassPoint::from(q.clone());
+            assert_eq!(WeierstrassPoint::from(sum), general_sum);
+        }
+    }
+
+    #[test]
+    fn weierstrass_binary_curves() {
+        const M: T = 3;
+        let modulo = Polynomial::new(vec![Bits8(0b1011)]);
+        let elements: Vec<F2m<M>> = (0..8)
+            .map(|i| F2m::new(Polynomial::new(vec![Bits8(i)]), modulo.clone()))
+            .collect();
+        let e = |i: usize| elements[i].clone();
+
+        // Ordinary curve with every coefficient present
+        let ec = WeierstrassEc::try_new(e(2), e(3), e(5), e(6), e(7)).unwrap();
+        let binary = WeierstrassEc::from(ec.to_binary().unwrap());
+        let points = weierstrass_points(&ec, &elements);
+        assert_eq!(ec.j_invariant(), binary.j_invariant());
+        check_group(&points);
+        for p in &points {
+            let image = WeierstrassPoint::from(p.to_binary().unwrap());
+            assert!(image.is_on_curve());
+            for q in &points {
+                let sum = WeierstrassPoint::from((p.clone() + q.clone()).to_binary().unwrap());
+                assert_eq!(
+                    sum,
+                    image.clone() + WeierstrassPoint::from(q.to_binary().unwrap())
+                );
+            }
+        }
+
+        // Supersingular y^2 + y = x^3 has q + 1 points and no binary short form
+        let supersingular = WeierstrassEc::try_new(e(0), e(0), e(1), e(0), e(0)).unwrap();
+        let points = weierstrass_points(&supersingular, &elements);
+        assert_eq!(points.len(), 9);
+        assert_eq!(supersingular.j_invariant(), Some(e(0)));
+        assert_eq!(supersingular.to_binary(), None);
+        check_group(&points);
+    }
+
+    #[test]
+    fn weierstrass_characteristic_three() {
+        let modulo: Polynomial<Fp<3>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let elements: Vec<Fpk<3, 2>> = (0..9)
+            .map(|i| Fpk::new(Polynomial::new_from_slice(&[i % 3, i / 3]), modulo.clone()))
+            .collect();
+        let (zero, one) = (elements[0].clone(), elements[1].clone());
+
+        // y^2 = x^3 + x^2 + 1 is ordinary and has no short form in characteristic 3
+        let ec =
+            WeierstrassEc::try_new(zero.clone(), one.clone(), zero.clone(), zero, one).unwrap();
+        assert_eq!(ec.to_short(), None);
+        assert_ne!(ec.j_invariant(), Some(elements[0].clone()));
+        check_group(&weierstrass_points(&ec, &elements));
+        assert!(
+            WeierstrassEc::try_new(
+                elements[0].clone(),
+                elements[0].clone(),
+                elements[0].clone(),
+                elements[0].clone(),
+                elements[1].clone()
+            )
+            .is_err()
+        );
+    }
+
+    /// Compares every projective operation with affine addition,
+    /// `rescale` gives another representative of the same projective point.
+    fn check_projective<F: Field>(
+        ec: &Ec<F>,
+        elements: &[F],
+        rescale: impl Fn(
+            &<Ec<F> as ProjectiveCalculations<F>>::Point,
+        ) -> <Ec<F> as ProjectiveCalculations<F>>::Point,
+    ) where
+        Ec<F>: ProjectiveCalculations<F>,
+        EcPoint<F>: Add<Output = EcPoint<F>> + Pow,
+    {
+        let points = ec_points(ec, elements);
+        let order = points.len();
+        for p in &points {
+            let p_proj = rescale(&ec.to_projective(p));
+            assert_eq!(ec.to_affine(&p_proj), *p);
+            assert_eq!(
+                ec.to_affine(&ec.double_projective(&p_proj)),
+                p.clone() + p.clone()
+            );
+            assert_eq!(p.clone().pow(order as T), EcPoint::Infinity);
+
+            let multiples = (1..order as T).scan(EcPoint::Infinity, |acc, _| {
+                *acc = acc.clone() + p.clone();
+                Some(acc.clone())
+            });
+            for (i, multiple) in multiples.enumerate() {
+                assert_eq!(p.clone().pow(i as T + 1), multiple);
+            }
+
+            for q in &points {
+                let q_proj = rescale(&ec.to_projective(q));
+                let sum = p.clone() + q.clone();
+                assert_eq!(ec.to_affine(&ec.add_projective(&p_proj, &q_proj)), sum);
+                if let EcPoint::Point { x, y, .. } = q {
+                    assert_eq!(ec.to_affine(&ec.add_mixed(&p_proj, (x, y))), sum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn jacobian_coordinates() {
+        const Q: T = 17;
+        let elements: Vec<Fp<Q>> = (0..Q).map(Fp::new).collect();
+        let lambda = Fp::<Q>::new(3);
+        let rescale = |p: &JacobianPoint<Fp<Q>>| JacobianPoint {
+            x: p.x * lambda.pow(2),
+            y: p.y * lambda.pow(3),
+            z: p.z * lambda,
+        };
+
+        check_projective(&Ec::new(Fp::new(2), Fp::new(2)), &elements, rescale);
+        // y^2 = x^3 - x has three points of order two
+        check_projective(&Ec::new(Fp::new(Q - 1), Fp::new(0)), &elements, rescale);
+    }
+
+    #[test]
+    fn jacobian_coordinates_over_fpk() {
+        let modulo: Polynomial<Fp<5>> = Polynomial::new_from_slice(&[2, 0, 1]);
+        let elements: Vec<Fpk<5, 2>> = (0..25)
+            .map(|i| Fpk::new(Polynomial::new_from_slice(&[i % 5, i / 5]), modulo.clone()))
+            .collect();
+        let lambda = elements[7].clone();
+        let rescale = |p: &JacobianPoint<Fpk<5, 2>>| JacobianPoint {
+            x: p.x.clone() * lambda.clone().pow(2),
+            y: p.y.clone() * lambda.clone().pow(3),
+            z: p.z.clone() * lambda.clone(),
+        };
+
+        check_projective(
+            &Ec::new(elements[1].clone(), elements[6].clone()),
+            &elements,
+            rescale,
+        );
+    }
+
+    #[test]
+    fn lopez_dahab_coordinates() {
+        const M: T = 3;
+        let modulo = Polynomial::new(vec![Bits8(0b1011)]);
+        let elements: Vec<F2m<M>> = (0..8)
+            .map(|i| F2m::new(Polynomial::new(vec![Bits8(i)]), modulo.clone()))
+            .collect();
+        let lambda = elements[6].clone();
+        let rescale = |p: &LopezDahabPoint<M>| LopezDahabPoint {
+            x: p.x.clone() * lambda.clone(),
+            y: p.y.clone() * lambda.clone().pow(2),
+            z: p.z.clone() * lambda.clone(),
+        };
+
+        check_projective(
+            &Ec::new(elements[1].clone(), elements[3].clone()),
+            &elements,
+            rescale,
+        );
+        check_projective(
+            &Ec::new(elements[0].clone(), elements[5].clone()),
+            &elements,
+            rescale,
+        );
+    }
+
+    fn check_sec1_round_trip<F: FieldBytes>(ec: &Ec<F>, elements: &[F])
+    where
+        Ec<F>: EcCalculations<F>,
+    {
+        for point in ec_points(ec, elements) {
+            for compressed in [false, true] {
+                let bytes = point.to_sec1(compressed);
+                assert_eq!(EcPoint::from_sec1(&bytes, ec), Ok(point.clone()));
+            }
+        }
+    }
+
+    #[test]
+    fn sec1_encoding() {
+        // secp112r1 from SEC 2
+        const P112: T = 0xdb7c2abf62e35e668076bead208b;
+        let ec: Ec<Fp<P112>> = Ec::new(
+            Fp::new(0xdb7c2abf62e35e668076bead2088),
+            Fp::new(0x659ef8ba043916eede8911702b22),
+        );
+        let uncompressed =
+            hex::decode("0409487239995a5ee76b55f9c2f098a89ce5af8724c0a23e0e0ff77500").unwrap();
+        let compressed = hex::decode("0209487239995a5ee76b55f9c2f098").unwrap();
+
+        let g = EcPoint::from_sec1(&uncompressed, &ec).unwrap();
+        assert_eq!(EcPoint::from_sec1(&compressed, &ec), Ok(g.clone()));
+        assert_eq!(g.to_sec1(false), uncompressed);
+        assert_eq!(g.to_sec1(true), compressed);
+        let minus_g = -g;
+        assert_eq!(minus_g.to_sec1(true)[0], 3);
+        assert_eq!(EcPoint::from_sec1(&minus_g.to_sec1(true), &ec), Ok(minus_g));
+        assert_eq!(EcPoint::<Fp<P112>>::Infinity.to_sec1(true), [0]);
+        assert_eq!(EcPoint::from_sec1(&[0], &ec), Ok(EcPoint::Infinity));
+
+        let mut p_as_x = hex::decode("02db7c2abf62e35e668076bead208b").unwrap();
+        let mut not_on_curve = uncompressed.clone();
+        not_on_curve[28] ^= 1;
+        for bytes in [
+            &[][..],
+            &[0, 0],
+            &uncompressed[..28],
+            &compressed[1..],
+            &p_as_x,
+        ] {
+            assert_eq!(
+                EcPoint::from_sec1(bytes, &ec),
+                Err(EcErrors::InvalidEncoding)
+            );
+        }
+        p_as_x[0] = 5;
+        assert_eq!(
+            EcPoint::from_sec1(&p_as_x, &ec),
+            Err(EcErrors::InvalidEncoding)
+        );
+        assert_eq!(
+            EcPoint::from_sec1(&not_on_curve, &ec),
+            Err(EcErrors::PointNotOnCurve)
+        );
+
+        let elements: Vec<Fp<P>> = (0..P).map(Fp::new).collect();
+        check_sec1_round_trip(&Ec::new(Fp::new(2), Fp::new(3)), &elements);
+    }
+
+    #[test]
+    fn sec1_encoding_binary() {
+        // sect113r1 from SEC 2, reduced by x^113 + x^9 + 1
+        let mut modulo = vec![Bits8(0); 15];
+        (modulo[0], modulo[1], modulo[14]) = (Bits8(1), Bits8(0b10), Bits8(0b10));
+        let one = F2m::<113>::new_from_slice(&[Bits8(1)], &modulo);
+        let element = |hex: &str| one.parse_fixed_bytes(&hex::decode(hex).unwrap()).unwrap();
+        let ec = Ec::new(
+            element("003088250ca6e7c7fe649ce85820f7"),
+            element("00e8bee4d3e2260744188be0e9c723"),
+        );
+        let uncompressed =
+            hex::decode("04009d73616f35f4ab1407d73562c10f00a52830277958ee84d1315ed31886").unwrap();
+
+        let g = EcPoint::from_sec1(&uncompressed, &ec).unwrap();
+        assert_eq!(g.to_sec1(false), uncompressed);
+        let compressed = g.to_sec1(true);
+        assert_eq!(
+            compressed,
+            hex::decode("03009d73616f35f4ab1407d73562c10f").unwrap()
+        );
+        assert_eq!(EcPoint::from_sec1(&compressed, &ec), Ok(g.clone()));
+        assert_eq!(EcPoint::from_sec1(&(-g.clone()).to_sec1(true), &ec), Ok(-g));
+
+        // x^113 does not fit
+        let mut too_wide = compressed.clone();
+        too_wide[1] = 0b10;
+        assert_eq!(
+            EcPoint::from_sec1(&too_wide, &ec),
+            Err(EcErrors::InvalidEncoding)
+        );
+
+        let modulo = Polynomial::new(vec![Bits8(0b100101)]);
+        let elements: Vec<F2m<5>> = (0..32)
+            .map(|i| F2m::new(Polynomial::new(vec![Bits8(i)]), modulo.clone()))
+            .collect();
+        check_sec1_round_trip(
+            &Ec::new(elements[1].clone(), elements[3].clone()),
+            &elements,
+        );
+    }
+
+    #[test]
+    fn point_counting() {
+        let elements: Vec<Fp<P>> = (0..P).map(Fp::new).collect();
+        for (a, b) in [(1, 1), (2, 3), (0, 7)] {
+            let ec = Ec::new(Fp::<P>::new(a), Fp::new(b));
+            let expected = count_points(&ec, &elements);
+            assert_eq!(ec.count_points_naive(), BigUint::from(expected));
+        }
+
+        let modulo: Polynomial<Fp<5>> = Polynomial::new_from_slice(&[2, 0, 1]);
+        let zero: Fpk<5, 2> = Fpk::new(Polynomial::new_from_slice(&[0]), modulo);
+        let elements: Vec<_> = (0..25).map(|i| zero.element_at(i)).collect();
+        let ec = Ec::new(elements[7].clone(), elements[1].clone());
+        assert_eq!(
+            ec.count_points_naive(),
+            BigUint::from(count_points(&ec, &elements))
+        );
+
+        let zero = F2m::<5>::new(
+            Polynomial::new(vec![]),
+            Polynomial::new(vec![Bits8(0b100101)]),
+        );
+        let elements: Vec<_> = (0..32).map(|i| zero.element_at(i)).collect();
+        let ec = Ec::new(elements[1].clone(), elements[6].clone());
+        assert_eq!(
+            ec.count_points_naive(),
+            BigUint::from(count_points(&ec, &elements))
+        );
+    }
+
+    #[test]
+    fn point_counting_bsgs() {
+        for (a, b) in [(1, 1), (3, 8), (0, 5), (11, 0)] {
+            let ec = Ec::new(Fp::<1009>::new(a), Fp::new(b));
+            assert_eq!(ec.count_points_bsgs(), Some(ec.count_points_naive()));
+        }
+
+        // x^2 + 1 is irreducible over F_47
+        let modulo: Polynomial<Fp<47>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let zero: Fpk<47, 2> = Fpk::new(Polynomial::new_from_slice(&[0]), modulo);
+        let ec = Ec::new(zero.element_at(100), zero.element_at(3));
+        assert_eq!(ec.count_points_bsgs(), Some(ec.count_points_naive()));
+
+        // x^11 + x^2 + 1
+        let modulo = Polynomial::new(vec![Bits8(0b101), Bits8(0b1000)]);
+        let zero = F2m::<11>::new(Polynomial::new(vec![]), modulo);
+        for (a, b) in [(0, 1), (1, 0x2a5), (0x400, 0x123)] {
+            let ec = Ec::new(zero.element_at(a), zero.element_at(b));
+            assert_eq!(ec.count_points_bsgs(), Some(ec.count_points_naive()));
+        }
+
+        let ec = Ec::new(Fp::<1000003>::new(3), Fp::new(5));
+        assert_eq!(ec.count_points(), BigUint::from(1001205_u32));
+    }
+
+    #[test]
+    fn point_counting_schoof() {
+        // Schoof's algorithm alone, without the baby-step giant-step finish
+        for (a, b) in [(1, 1), (3, 8), (0, 5), (11, 0)] {
+            let ec = Ec::new(Fp::<1009>::new(a), Fp::new(b));
+            assert_eq!(ec.count_points_schoof_bsgs(0), ec.count_points_naive());
+        }
+
+        let modulo: Polynomial<Fp<47>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let zero: Fpk<47, 2> = Fpk::new(Polynomial::new_from_slice(&[0]), modulo);
+        let ec = Ec::new(zero.element_at(100), zero.element_at(3));
+        assert_eq!(ec.count_points_schoof_bsgs(0), ec.count_points_naive());
+
+        let ec = Ec::new(Fp::<1000003>::new(3), Fp::new(5));
+        assert_eq!(ec.count_points_schoof_bsgs(0), BigUint::from(1001205_u32));
+
+        const Q: T = (1 << 40) - 87;
+        for (a, b) in [(Q - 3, 7), (2, 0x1234567)] {
+            let ec = Ec::new(Fp::<Q>::new(a), Fp::new(b));
+            let expected = ec.count_points_bsgs().unwrap();
+            assert_eq!(ec.count_points_schoof_bsgs(0), expected);
+            assert_eq!(ec.count_points_schoof_bsgs(1 << 8), expected);
+            assert_eq!(ec.count_points_schoof(), expected);
+        }
+    }
+
+    #[test]
+    fn point_orders() {
+        use crate::curves;
+
+        let small = curves::small_f23();
+        assert_eq!(small.ec.count_points(), BigUint::from(28_u32));
+        assert_eq!(small.g.order(), small.n);
+        assert_eq!(EcPoint::<Fp<23>>::Infinity.order(), BigUint::from(1_u32));
+        let (g, p) = small.ec.prime_order_subgroup().unwrap();
+        assert_eq!(p, BigUint::from(7_u32));
+        assert_eq!(g.order(), p);
+
+        // 28 = 2^2 * 7, but the factors only need to give some multiple of the order
+        let factors = [(BigUint::from(2_u32), 3), (BigUint::from(7_u32), 1)];
+        assert_eq!(small.g.order_from_factors(&factors), small.n);
+
+        let binary = curves::small_f2_5();
+        assert_eq!(binary.ec.count_points(), binary.order());
+        assert_eq!(binary.g.order(), binary.n);
+        assert_eq!(curves::small_f17().g.order(), BigUint::from(19_u32));
+    }
 }
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::ops::Neg;
+
+use num_bigint::BigUint;
+
+use super::{Ec, EcPoint};
+use crate::{
+    T,
+    arithmetic::{factor, gcd},
+    scalar::ScalarMul,
+    traits::{EcCalculations, FiniteField, ProjectiveCalculations},
+};
+
+/// Fields up to this size are counted point by point.
+const NAIVE_LIMIT: T = 1 << 12;
+/// Points tried on the curve and on its twist before `count_points_bsgs` gives up.
+const MESTRE_ATTEMPTS: T = 32;
+/// Candidate orders are only checked once the exponents leave at most this many.
+const CANDIDATE_LIMIT: T = 1 << 12;
+
+impl<F> Ec<F>
+where
+    F: FiniteField,
+    Ec<F>: ProjectiveCalculations<F>,
+    EcPoint<F>: Neg<Output = EcPoint<F>>,
+{
+    /// Number of points including infinity, taking the naive count for tiny fields
+    /// and `count_points_bsgs` otherwise.
+    pub fn count_points(&self) -> BigUint {
+        let q = self.field_size();
+        match q <= NAIVE_LIMIT {
+            true => self.count_points_naive(),
+            false => self
+                .count_points_bsgs()
+                .expect("Could not determine the number of points"),
+        }
+    }
+
+    /// Counts the points by lifting every element of the field to the curve.
+    pub fn count_points_naive(&self) -> BigUint {
+        let count: T = (0..self.field_size())
+            .filter_map(|i| self.get_point_on_curve(self.a.element_at(i), false).ok())
+            .map(|point| if point == -point.clone() { 1 } else { 2 })
+            .sum();
+        BigUint::from(count + 1)
+    }
+
+    /// Mestre's algorithm: baby-step giant-step finds the orders of points in the Hasse
+    /// interval, on the curve and on its quadratic twist, until a single group order
+    /// is consistent with both. Uses about q^(1/4) group operations.
+    /// Returns `None` when the points tried do not pin down the order, which only
+    /// happens for very small fields.
+    pub fn count_points_bsgs(&self) -> Option<BigUint> {
+        let q = self.field_size();
+        let (lo, hi) = hasse_interval(q);
+        let twist = self.quadratic_twist(self.twist_parameter());
+        // #E + #E' = 2q + 2, so a multiple of the twist's exponent pins #E down too
+        let (mut exponent, mut twist_exponent) = (1, 1);
+        for seed in 0..MESTRE_ATTEMPTS {
+            if let Some(point) = self.point_from_seed(seed) {
+                exponent = lcm(exponent, point.order_in_interval(lo, hi)?);
+            }
+            if let Some(point) = twist.point_from_seed(seed) {
+                twist_exponent = lcm(twist_exponent, point.order_in_interval(lo, hi)?);
+            }
+
+            let step = exponent.max(twist_exponent);
+            if (hi - lo) / step > CANDIDATE_LIMIT {
+                continue;
+            }
+            let mut consistent = multiples_in(step, lo, hi)
+                .map(|k| match step == exponent {
+                    true => (k, 2 * q + 2 - k),
+                    false => (2 * q + 2 - k, k),
+                })
+                .filter(|(n, n_twist)| n % exponent == 0 && n_twist % twist_exponent == 0)
+                .map(|(n, _)| n);
+            if let (Some(n), None) = (consistent.next(), consistent.next()) {
+                return Some(BigUint::from(n));
+            }
+        }
+        None
+    }
+
+    /// A point G of the largest prime order p dividing the number of points, with p.
+    /// `None` when the curve has no points besides infinity.
+    pub fn prime_order_subgroup(&self) -> Option<(EcPoint<F>, BigUint)> {
+        let n = T::try_from(self.count_points()).unwrap();
+        let &(p, _) = factor(n).last()?;
+        let cofactor = n / p;
+        (0..)
+            .filter_map(|seed| self.point_from_seed(seed))
+            .map(|point| point.mul_vartime(&cofactor))
+            .find(|g| !g.is_identity())
+            .map(|g| (g, BigUint::from(p)))
+    }
+
+    /// Number of points when it is known to be `residue` modulo `modulus`. Searches the
+    /// candidates in the Hasse interval by baby-step giant-step for the only one that
+    /// kills a point, of the curve or, as #E + #E' = 2q + 2, of its twist.
+    /// `None` when no point tried singles one out.
+    pub(super) fn count_points_in_progression(
+        &self,
+        residue: &BigUint,
+        modulus: &BigUint,
+    ) -> Option<BigUint> {
+        let q = self.a.order();
+        let width = (&q * 4_u8).sqrt();
+        let lo = (&q + 1_u8 - &width).max(BigUint::from(1_u8));
+        let hi = &q + 1_u8 + width;
+        let twist_sum = &q * 2_u8 + 2_u8;
+        let twist_residue = (&twist_sum % modulus + modulus - residue) % modulus;
+
+        let twist = self.quadratic_twist(self.twist_parameter());
+        for seed in 0..MESTRE_ATTEMPTS {
+            for (on_twist, ec, residue) in [(false, self, residue), (true, &twist, &twist_residue)]
+            {
+                let first = &lo + (residue + modulus - &lo % modulus) % modulus;
+                if first > hi {
+                    return None;
+                }
+                let count = T::try_from((&hi - &first) / modulus).ok()? + 1;
+                let Some(n) = ec
+                    .point_from_seed(seed)
+                    .and_then(|point| point.unique_in_progression(&first, modulus, count))
+                else {
+                    continue;
+                };
+                return Some(match on_twist {
+                    false => n,
+                    true => twist_sum - n,
+                });
+            }
+        }
+        None
+    }
+
+    fn field_size(&self) -> T {
+        T::try_from(self.a.order()).expect("Field is too large to count points")
+    }
+
+    /// A point picked pseudo-randomly by the seed, `None` if the curve has no affine points.
+    fn point_from_seed(&self, seed: T) -> Option<EcPoint<F>> {
+        let q = T::try_from(self.a.order()).unwrap_or(T::MAX);
+        let start = seed.wrapping_mul(0x9e3779b97f4a7c15f39cc0605cedc835) % q;
+        (0..q)
+            .map(|i| self.a.element_at(start.wrapping_add(i) % q))
+            .find_map(|x| self.get_point_on_curve(x, seed % 2 == 1).ok())
+    }
+}
+
+impl<F> EcPoint<F>
+where
+    F: FiniteField,
+    Ec<F>: ProjectiveCalculations<F>,
+    EcPoint<F>: Neg<Output = EcPoint<F>>,
+{
+    /// Order of the point given the factorization of a multiple of it, such as the group order.
+    pub fn order_from_factors(&self, factors: &[(BigUint, u32)]) -> BigUint {
+        let mut order: BigUint = factors.iter().map(|(p, e)| p.pow(*e)).product();
+        assert!(
+            self.mul_vartime(&order).is_identity(),
+            "The factors must multiply to a multiple of the order"
+        );
+        for (p, e) in factors {
+            for _ in 0..*e {
+                let candidate = &order / p;
+                if !self.mul_vartime(&candidate).is_identity() {
+                    break;
+                }
+                order = candidate;
+            }
+        }
+        order
+    }
+
+    /// Order of the point, from the number of points on its curve.
+    pub fn order(&self) -> BigUint {
+        let Some(ec) = self.curve() else {
+            return BigUint::from(1_u8);
+        };
+        let n = T::try_from(ec.count_points()).unwrap();
+        self.order_from_factors(&big_factors(n))
+    }
+
+    /// Order of the point, given that some multiple of it lies in [lo, hi].
+    fn order_in_interval(&self, lo: T, hi: T) -> Option<T> {
+        let multiple = self.multiple_in_interval(lo, hi)?;
+        T::try_from(self.order_from_factors(&big_factors(multiple))).ok()
+    }
+
+    /// Some M in [lo, hi] with M * P = O.
+    fn multiple_in_interval(&self, lo: T, hi: T) -> Option<T> {
+        self.progression_solution(&BigUint::from(lo), &BigUint::from(1_u8), hi - lo + 1)
+            .map(|k| lo + k)
+    }
+
+    /// start + k * step for the only k < count with (start + k * step) * P = O.
+    fn unique_in_progression(&self, start: &BigUint, step: &BigUint, count: T) -> Option<BigUint> {
+        let at = |k: T| start + step * k;
+        let k = self.progression_solution(start, step, count)?;
+        let before = self.progression_solution(start, step, k);
+        let after = self.progression_solution(&at(k + 1), step, count - k - 1);
+        match (before, after) {
+            (None, None) => Some(at(k)),
+            _ => None,
+        }
+    }
+
+    /// Baby-step giant-step search for some k < count with (start + k * step) * P = O.
+    /// With Q = step * P and R = start * P, the baby steps j * Q for j <= m are stored
+    /// by x, so a giant step R + c * Q matching one of them gives k = c - j or k = c + j.
+    fn progression_solution(&self, start: &BigUint, step: &BigUint, count: T) -> Option<T> {
+        if count == 0 {
+            return None;
+        }
+        let (q, r) = (self.mul_vartime(step), self.mul_vartime(start));
+        let m = (count - 1).isqrt() + 1;
+        let mut baby_steps = HashMap::new();
+        let mut point = q.clone();
+        for j in 1..=m {
+            let EcPoint::Point { x, .. } = &point else {
+                // Q has order j, so k can be taken below j
+                return (0..j.min(count)).find(|k| (r.clone() + q.mul_vartime(k)).is_identity());
+            };
+            baby_steps.insert(x.clone(), (j, point.clone()));
+            point = point + q.clone();
+        }
+
+        let giant_step = q.mul_vartime(&(2 * m + 1));
+        let mut c = m;
+        let mut giant = r + q.mul_vartime(&c);
+        while c - m < count {
+            let k = match &giant {
+                EcPoint::Infinity => Some(c),
+                EcPoint::Point { x, .. } => baby_steps
+                    .get(x)
+                    .map(|(j, baby)| if giant == *baby { c - j } else { c + j }),
+            };
+            if let Some(k) = k.filter(|&k| k < count) {
+                return Some(k);
+            }
+            giant = giant + giant_step.clone();
+            c += 2 * m + 1;
+        }
+        None
+    }
+}
+
+/// [q + 1 - 2 sqrt(q), q + 1 + 2 sqrt(q)], without zero.
+fn hasse_interval(q: T) -> (T, T) {
+    let width = (4 * q).isqrt();
+    ((q + 1 - width).max(1), q + 1 + width)
+}
+
+fn lcm(a: T, b: T) -> T {
+    a / gcd(a, b) * b
+}
+
+fn multiples_in(step: T, lo: T, hi: T) -> impl Iterator<Item = T> {
+    (lo.div_ceil(step)..=hi / step).map(move |k| k * step)
+}
+
+fn big_factors(n: T) -> Vec<(BigUint, u32)> {
+    factor(n)
+        .into_iter()
+        .map(|(p, e)| (BigUint::from(p), e))
+        .collect()
+}
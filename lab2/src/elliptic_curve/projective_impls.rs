@@ -0,0 +1,278 @@
+use super::ec_point_impls::from_int;
+use super::{Ec, EcPoint, JacobianPoint, LopezDahabPoint};
+use crate::{
+    T,
+    f2m::F2m,
+    scalar::ConditionalSwap,
+    traits::{Field, FiniteField, Inverse, Normal, Parity, Pow, ProjectiveCalculations, Sqrt},
+};
+
+impl<T: Field + ConditionalSwap> ConditionalSwap for JacobianPoint<T> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.x.conditional_swap(&mut other.x, mask);
+        self.y.conditional_swap(&mut other.y, mask);
+        self.z.conditional_swap(&mut other.z, mask);
+    }
+}
+
+impl<const M: T> ConditionalSwap for LopezDahabPoint<M> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        self.x.conditional_swap(&mut other.x, mask);
+        self.y.conditional_swap(&mut other.y, mask);
+        self.z.conditional_swap(&mut other.z, mask);
+    }
+}
+
+impl<T> ProjectiveCalculations<T> for Ec<T>
+where
+    T: FiniteField + Normal + Sqrt + Parity + Inverse + ConditionalSwap,
+{
+    type Point = JacobianPoint<T>;
+
+    fn to_projective(&self, point: &EcPoint<T>) -> JacobianPoint<T> {
+        match point {
+            EcPoint::Point { x, y, .. } => JacobianPoint {
+                x: x.clone(),
+                y: y.clone(),
+                z: x.one(),
+            },
+            EcPoint::Infinity => JacobianPoint {
+                x: self.a.one(),
+                y: self.a.one(),
+                z: self.a.zero(),
+            },
+        }
+    }
+
+    fn to_affine(&self, point: &JacobianPoint<T>) -> EcPoint<T> {
+        let JacobianPoint { x, y, z } = point.clone();
+        if z == z.zero() {
+            return EcPoint::Infinity;
+        }
+
+        let z_inv = z.inv();
+        let z_inv_sq = z_inv.clone().pow(2);
+        EcPoint::Point {
+            x: x * z_inv_sq.clone(),
+            y: y * z_inv_sq * z_inv,
+            ec: self.clone(),
+        }
+    }
+
+    fn add_projective(&self, p1: &JacobianPoint<T>, p2: &JacobianPoint<T>) -> JacobianPoint<T> {
+        let zero = self.a.zero();
+        if p1.z == zero {
+            return p2.clone();
+        }
+        if p2.z == zero {
+            return p1.clone();
+        }
+
+        let z1_sq = p1.z.clone().pow(2);
+        let z2_sq = p2.z.clone().pow(2);
+        let u1 = p1.x.clone() * z2_sq.clone();
+        let u2 = p2.x.clone() * z1_sq.clone();
+        let s1 = p1.y.clone() * p2.z.clone() * z2_sq;
+        let s2 = p2.y.clone() * p1.z.clone() * z1_sq;
+        let z = p1.z.clone() * p2.z.clone();
+        self.add_jacobian(p1, (u1, s1), (u2, s2), z)
+    }
+
+    fn add_mixed(&self, p1: &JacobianPoint<T>, (x2, y2): (&T, &T)) -> JacobianPoint<T> {
+        if p1.z == p1.z.zero() {
+            return self.to_projective(&EcPoint::new_unchecked(
+                x2.clone(),
+                y2.clone(),
+                self.clone(),
+            ));
+        }
+
+        let z1_sq = p1.z.clone().pow(2);
+        let u2 = x2.clone() * z1_sq.clone();
+        let s2 = y2.clone() * p1.z.clone() * z1_sq;
+        self.add_jacobian(p1, (p1.x.clone(), p1.y.clone()), (u2, s2), p1.z.clone())
+    }
+
+    fn double_projective(&self, p: &JacobianPoint<T>) -> JacobianPoint<T> {
+        let JacobianPoint { x, y, z } = p.clone();
+        let zero = x.zero();
+        if z == zero || y == zero {
+            return self.to_projective(&EcPoint::Infinity);
+        }
+
+        let n = |k| from_int(&x, k);
+        let y_sq = y.clone().pow(2);
+        let s = n(4) * x.clone() * y_sq.clone();
+        let m = n(3) * x.clone().pow(2) + self.a.clone() * z.clone().pow(4);
+        let x_new = m.clone().pow(2) - n(2) * s.clone();
+        JacobianPoint {
+            y: m * (s - x_new.clone()) - n(8) * y_sq.pow(2),
+            x: x_new,
+            z: n(2) * y * z,
+        }
+    }
+}
+
+impl<T> Ec<T>
+where
+    T: FiniteField + Normal + Sqrt + Parity + Inverse + ConditionalSwap,
+{
+    /// Shared part of the full and mixed addition. Takes both points scaled to the
+    /// common denominator (U = X Z'^2, S = Y Z'^3) and the product of the Z coordinates.
+    fn add_jacobian(
+        &self,
+        p1: &JacobianPoint<T>,
+        (u1, s1): (T, T),
+        (u2, s2): (T, T),
+        z: T,
+    ) -> JacobianPoint<T> {
+        let h = u2 - u1.clone();
+        let r = s2 - s1.clone();
+        if h == h.zero() {
+            return match r == r.zero() {
+                true => self.double_projective(p1),
+                false => self.to_projective(&EcPoint::Infinity),
+            };
+        }
+
+        let h_sq = h.clone().pow(2);
+        let h_cubed = h_sq.clone() * h.clone();
+        let v = u1 * h_sq;
+        let x = r.clone().pow(2) - h_cubed.clone() - from_int(&v, 2) * v.clone();
+        JacobianPoint {
+            y: r * (v - x.clone()) - s1 * h_cubed,
+            x,
+            z: z * h,
+        }
+    }
+}
+
+impl<const M: T> ProjectiveCalculations<F2m<M>> for Ec<F2m<M>> {
+    type Point = LopezDahabPoint<M>;
+
+    fn to_projective(&self, point: &EcPoint<F2m<M>>) -> LopezDahabPoint<M> {
+        match point {
+            EcPoint::Point { x, y, .. } => LopezDahabPoint {
+                x: x.clone(),
+                y: y.clone(),
+                z: x.one(),
+            },
+            EcPoint::Infinity => LopezDahabPoint {
+                x: self.a.one(),
+                y: self.a.zero(),
+                z: self.a.zero(),
+            },
+        }
+    }
+
+    fn to_affine(&self, point: &LopezDahabPoint<M>) -> EcPoint<F2m<M>> {
+        let LopezDahabPoint { x, y, z } = point.clone();
+        if z.is_zero() {
+            return EcPoint::Infinity;
+        }
+
+        let z_inv = z.inv();
+        EcPoint::Point {
+            x: x * z_inv.clone(),
+            y: y * z_inv.pow(2),
+            ec: self.clone(),
+        }
+    }
+
+    /// add-2005-dl from the Explicit-Formulas Database.
+    fn add_projective(
+        &self,
+        p1: &LopezDahabPoint<M>,
+        p2: &LopezDahabPoint<M>,
+    ) -> LopezDahabPoint<M> {
+        if p1.z.is_zero() {
+            return p2.clone();
+        }
+        if p2.z.is_zero() {
+            return p1.clone();
+        }
+
+        let a1 = p1.x.clone() * p2.z.clone();
+        let a2 = p2.x.clone() * p1.z.clone();
+        let c = a1.clone().pow(2);
+        let d = a2.clone().pow(2);
+        let e = a1.clone() + a2.clone();
+        let g = p1.y.clone() * p2.z.clone().pow(2);
+        let h = p2.y.clone() * p1.z.clone().pow(2);
+        let i = g.clone() + h.clone();
+        if e.is_zero() {
+            return match i.is_zero() {
+                true => self.double_projective(p1),
+                false => self.to_projective(&EcPoint::Infinity),
+            };
+        }
+
+        let f = c.clone() + d.clone();
+        let j = i * e;
+        let z = f.clone() * p1.z.clone() * p2.z.clone();
+        let x = a1.clone() * (h + d) + a2 * (c + g.clone());
+        LopezDahabPoint {
+            y: (a1 * j.clone() + f.clone() * g) * f + (j + z.clone()) * x.clone(),
+            x,
+            z,
+        }
+    }
+
+    /// Mixed addition from Al-Daoud et al., as given in Hankerson, Menezes and Vanstone.
+    fn add_mixed(
+        &self,
+        p1: &LopezDahabPoint<M>,
+        (x2, y2): (&F2m<M>, &F2m<M>),
+    ) -> LopezDahabPoint<M> {
+        if p1.z.is_zero() {
+            return self.to_projective(&EcPoint::new_unchecked(
+                x2.clone(),
+                y2.clone(),
+                self.clone(),
+            ));
+        }
+
+        let z1_sq = p1.z.clone().pow(2);
+        let a = y2.clone() * z1_sq.clone() + p1.y.clone();
+        let b = x2.clone() * p1.z.clone() + p1.x.clone();
+        if b.is_zero() {
+            return match a.is_zero() {
+                true => self.double_projective(p1),
+                false => self.to_projective(&EcPoint::Infinity),
+            };
+        }
+
+        let c = p1.z.clone() * b.clone();
+        let d = b.pow(2) * (c.clone() + self.a.clone() * z1_sq);
+        let z = c.clone().pow(2);
+        let e = a.clone() * c;
+        let x = a.pow(2) + d + e.clone();
+        let f = x.clone() + x2.clone() * z.clone();
+        let g = (x2.clone() + y2.clone()) * z.clone().pow(2);
+        LopezDahabPoint {
+            y: (e + z.clone()) * f + g,
+            x,
+            z,
+        }
+    }
+
+    fn double_projective(&self, p: &LopezDahabPoint<M>) -> LopezDahabPoint<M> {
+        let LopezDahabPoint { x, y, z } = p.clone();
+        // Points with x = 0 have order two
+        if z.is_zero() || x.is_zero() {
+            return self.to_projective(&EcPoint::Infinity);
+        }
+
+        let x_sq = x.pow(2);
+        let z_sq = z.pow(2);
+        let z_new = x_sq.clone() * z_sq.clone();
+        let b_z4 = self.b.clone() * z_sq.pow(2);
+        let x_new = x_sq.pow(2) + b_z4.clone();
+        LopezDahabPoint {
+            y: b_z4.clone() * z_new.clone()
+                + x_new.clone() * (self.a.clone() * z_new.clone() + y.pow(2) + b_z4),
+            x: x_new,
+            z: z_new,
+        }
+    }
+}
@@ -0,0 +1,326 @@
+use num_bigint::BigUint;
+
+use super::{Ec, ec_point_impls::from_int};
+use crate::{
+    T,
+    arithmetic::pow_mod,
+    polynomials::Polynomial,
+    traits::{Field, FiniteField, Inverse, Normal, ProjectiveCalculations},
+};
+
+/// Once the primes leave at most this many candidates in the Hasse interval,
+/// baby-step giant-step finds the order faster than larger primes would.
+const BSGS_CANDIDATES: T = 1 << 24;
+
+/// A nontrivial monic factor of the modulus, found when an element was not invertible.
+type Split<F> = Polynomial<F>;
+
+/// Point (X(x), y * Y(x)) on the curve over F_q[x, y] / (h(x), y^2 - f(x)), where h divides
+/// a division polynomial, so that it stands for all the torsion points with x a root of h.
+#[derive(Clone, PartialEq)]
+enum TorsionPoint<F> {
+    Point(Polynomial<F>, Polynomial<F>),
+    Infinity,
+}
+
+/// Arithmetic modulo a monic factor h of a division polynomial.
+struct Torsion<F> {
+    modulus: Polynomial<F>,
+    /// x^3 + ax + b reduced modulo h.
+    f: Polynomial<F>,
+    a: F,
+}
+
+impl<F> Ec<F>
+where
+    F: FiniteField + Normal + Inverse,
+    Ec<F>: ProjectiveCalculations<F>,
+{
+    /// Schoof's algorithm: finds the trace t of Frobenius modulo small primes l from the
+    /// action of Frobenius on the l-torsion and puts them together with the CRT.
+    /// Polynomial in log q, so it reaches sizes that `count_points_bsgs` cannot.
+    /// The last factor of about 2^24 is left to baby-step giant-step.
+    /// The characteristic must be above 3.
+    pub fn count_points_schoof(&self) -> BigUint {
+        self.count_points_schoof_bsgs(BSGS_CANDIDATES)
+    }
+
+    /// Schoof's algorithm switching to baby-step giant-step once the trace is known
+    /// modulo enough primes to leave at most `candidates` possible orders.
+    pub(super) fn count_points_schoof_bsgs(&self, candidates: T) -> BigUint {
+        let zero = self.a.zero();
+        assert!(
+            from_int(&zero, 6) != zero,
+            "Schoof's algorithm needs characteristic above 3"
+        );
+
+        // |t| <= 2 sqrt(q), so the product of the primes has to exceed 4 sqrt(q)
+        let q = self.a.order();
+        let mut primes = Vec::new();
+        let mut product = BigUint::from(1_u8);
+        for l in (2..).filter(|&l: &T| (2..l).all(|d| l % d != 0)) {
+            if &product * &product > &q * 16_u8 {
+                break;
+            }
+            if (&q % l) != BigUint::ZERO {
+                primes.push(l);
+                product *= l;
+            }
+        }
+
+        let mut psi = Vec::new();
+        let (mut t, mut modulus) = (BigUint::ZERO, BigUint::from(1_u8));
+        let hasse_width = (&q * 16_u8).sqrt();
+        for &l in &primes {
+            if &hasse_width / &modulus <= BigUint::from(candidates) {
+                let residue = (&q + 1_u8 + &modulus - &t % &modulus) % &modulus;
+                if let Some(n) = self.count_points_in_progression(&residue, &modulus) {
+                    return n;
+                }
+            }
+
+            let t_mod_l = match l {
+                2 => self.trace_mod_two(&q),
+                _ => {
+                    if psi.len() <= l as usize {
+                        psi = self.division_polynomials(l as usize);
+                    }
+                    self.trace_mod(l, &q, &psi[l as usize])
+                }
+            };
+            // t + k * modulus = t_mod_l (mod l)
+            let (t_l, m_l) = (small_mod(&t, l), small_mod(&modulus, l));
+            let k = (t_mod_l + l - t_l) % l * pow_mod(m_l, l - 2, l) % l;
+            t += &modulus * k;
+            modulus *= l;
+        }
+
+        match &t * 2_u8 > modulus {
+            true => q + 1_u8 + (modulus - t),
+            false => q + 1_u8 - t,
+        }
+    }
+
+    /// Division polynomials ψ_0, ..., ψ_n, with the even ones divided by y
+    /// so that all of them are polynomials in x alone.
+    fn division_polynomials(&self, n: usize) -> Vec<Polynomial<F>> {
+        let (a, b) = (self.a.clone(), self.b.clone());
+        let (zero, one) = (a.zero(), a.one());
+        let c = |k| from_int(&a, k);
+        let f = Polynomial::new(vec![b.clone(), a.clone(), zero.clone(), one.clone()]);
+        let f_sq = f.clone() * f;
+
+        let a_sq = a.clone() * a.clone();
+        let psi_4 = [
+            -(c(8) * b.clone() * b.clone()) - a_sq.clone() * a.clone(),
+            -(c(4) * a.clone() * b.clone()),
+            -(c(5) * a_sq.clone()),
+            c(20) * b.clone(),
+            c(5) * a.clone(),
+            zero.clone(),
+            one.clone(),
+        ];
+        let mut psi = vec![
+            Polynomial::new(vec![]),
+            Polynomial::new(vec![one]),
+            Polynomial::new(vec![c(2)]),
+            Polynomial::new(vec![-a_sq, c(12) * b, c(6) * a.clone(), zero, c(3)]),
+            Polynomial::new(psi_4.into_iter().map(|x| x * c(4)).collect()),
+        ];
+        let half = Polynomial::new(vec![c(2).inv()]);
+        let cube = |p: &Polynomial<F>| p.clone() * p.clone() * p.clone();
+        let square = |p: &Polynomial<F>| p.clone() * p.clone();
+        for k in psi.len()..=n {
+            let m = k / 2;
+            let next = match k % 2 {
+                1 => {
+                    let left = psi[m + 2].clone() * cube(&psi[m]);
+                    let right = psi[m - 1].clone() * cube(&psi[m + 1]);
+                    match m % 2 {
+                        0 => f_sq.clone() * left - right,
+                        _ => left - f_sq.clone() * right,
+                    }
+                }
+                _ => {
+                    let inner = psi[m + 2].clone() * square(&psi[m - 1])
+                        - psi[m - 2].clone() * square(&psi[m + 1]);
+                    psi[m].clone() * inner * half.clone()
+                }
+            };
+            psi.push(next);
+        }
+        psi.truncate(n + 1);
+        psi
+    }
+
+    /// t is even exactly when the curve has a point of order two,
+    /// that is when x^3 + ax + b has a root, a common factor with x^q - x.
+    fn trace_mod_two(&self, q: &BigUint) -> T {
+        let (zero, one) = (self.a.zero(), self.a.one());
+        let f = Polynomial::new(vec![
+            self.b.clone(),
+            self.a.clone(),
+            zero.clone(),
+            one.clone(),
+        ]);
+        let torsion = Torsion::new(self, f.clone());
+        let x = Polynomial::new(vec![zero, one]);
+        let x_q_minus_x = torsion.pow(&x, q) - x;
+        match x_q_minus_x.is_zero() || inverse_mod(&x_q_minus_x, &f).is_err() {
+            true => 0,
+            false => 1,
+        }
+    }
+
+    /// t modulo an odd prime l, retrying on a factor of ψ_l whenever one turns up.
+    fn trace_mod(&self, l: T, q: &BigUint, psi: &Polynomial<F>) -> T {
+        let mut modulus = psi.monic();
+        loop {
+            match Torsion::new(self, modulus).trace(l, q) {
+                Ok(t) => return t,
+                Err(factor) => modulus = factor,
+            }
+        }
+    }
+}
+
+impl<F> Torsion<F>
+where
+    F: Field + Inverse,
+{
+    fn new(ec: &Ec<F>, modulus: Polynomial<F>) -> Self {
+        let (zero, one) = (ec.a.zero(), ec.a.one());
+        let f = Polynomial::new(vec![ec.b.clone(), ec.a.clone(), zero, one]);
+        Self {
+            f: f.div_rem(&modulus).1,
+            modulus,
+            a: ec.a.clone(),
+        }
+    }
+
+    /// Finds τ with π^2(P) + q P = τ π(P) on the points of the modulus,
+    /// which is t modulo l by the characteristic equation of Frobenius.
+    fn trace(&self, l: T, q: &BigUint) -> Result<T, Split<F>> {
+        let (zero, one) = (self.a.zero(), self.a.one());
+        let x = Polynomial::new(vec![zero, one.clone()]);
+        let exponent = (q - 1_u8) / 2_u8;
+        let frobenius = TorsionPoint::Point(self.pow(&x, q), self.pow(&self.f, &exponent));
+        let q_sq = q * q;
+        let frobenius_sq = TorsionPoint::Point(
+            self.pow(&x, &q_sq),
+            self.pow(&self.f, &((q_sq - 1_u8) / 2_u8)),
+        );
+
+        let q_mod_l = small_mod(q, l);
+        let point = TorsionPoint::Point(x, Polynomial::new(vec![one]));
+        let lhs = self.add(&frobenius_sq, &self.mul(&point, q_mod_l)?)?;
+        let TorsionPoint::Point(lhs_x, lhs_y) = lhs else {
+            return Ok(0);
+        };
+
+        let mut rhs = frobenius.clone();
+        for tau in 1..=l / 2 {
+            let TorsionPoint::Point(x, y) = &rhs else {
+                unreachable!("Multiples of π(P) below l are not the point at infinity");
+            };
+            if self.is_zero(&(lhs_x.clone() - x.clone()))? {
+                return match self.is_zero(&(lhs_y.clone() - y.clone()))? {
+                    true => Ok(tau),
+                    false => Ok(l - tau),
+                };
+            }
+            rhs = self.add(&rhs, &frobenius)?;
+        }
+        panic!("No trace of Frobenius modulo {l}, the curve must be singular");
+    }
+
+    fn add(&self, p1: &TorsionPoint<F>, p2: &TorsionPoint<F>) -> Result<TorsionPoint<F>, Split<F>> {
+        let (TorsionPoint::Point(x1, y1), TorsionPoint::Point(x2, y2)) = (p1, p2) else {
+            return Ok(match p1 {
+                TorsionPoint::Infinity => p2.clone(),
+                _ => p1.clone(),
+            });
+        };
+        if self.is_zero(&(x2.clone() - x1.clone()))? {
+            return match self.is_zero(&(y1.clone() + y2.clone()))? {
+                true => Ok(TorsionPoint::Infinity),
+                false => self.double(p1),
+            };
+        }
+
+        // The slope is y * L, and y^2 = f
+        let slope = self.mul_mod(
+            &(y2.clone() - y1.clone()),
+            &self.inv(&(x2.clone() - x1.clone()))?,
+        );
+        let x3 = self.mul_mod(&self.f, &self.mul_mod(&slope, &slope)) - x1.clone() - x2.clone();
+        let y3 = self.mul_mod(&slope, &(x1.clone() - x3.clone())) - y1.clone();
+        Ok(TorsionPoint::Point(x3, y3))
+    }
+
+    fn double(&self, p: &TorsionPoint<F>) -> Result<TorsionPoint<F>, Split<F>> {
+        let TorsionPoint::Point(x, y) = p else {
+            return Ok(TorsionPoint::Infinity);
+        };
+        if self.is_zero(y)? {
+            return Ok(TorsionPoint::Infinity);
+        }
+
+        // (3x^2 + a) / (2 y Y) = y (3x^2 + a) / (2 f Y)
+        let c = |k| Polynomial::new(vec![from_int(&self.a, k)]);
+        let numerator = c(3) * self.mul_mod(x, x) + Polynomial::new(vec![self.a.clone()]);
+        let denominator = c(2) * self.mul_mod(&self.f, y);
+        let slope = self.mul_mod(&numerator, &self.inv(&denominator)?);
+        let x3 = self.mul_mod(&self.f, &self.mul_mod(&slope, &slope)) - c(2) * x.clone();
+        let y3 = self.mul_mod(&slope, &(x.clone() - x3.clone())) - y.clone();
+        Ok(TorsionPoint::Point(x3, y3))
+    }
+
+    fn mul(&self, p: &TorsionPoint<F>, k: T) -> Result<TorsionPoint<F>, Split<F>> {
+        let mut result = TorsionPoint::Infinity;
+        for i in (0..T::BITS - k.leading_zeros()).rev() {
+            result = self.double(&result)?;
+            if (k >> i) & 1 == 1 {
+                result = self.add(&result, p)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Whether the element vanishes on all roots of the modulus. It may vanish on just
+    /// some of them, which splits the modulus.
+    fn is_zero(&self, a: &Polynomial<F>) -> Result<bool, Split<F>> {
+        match a.div_rem(&self.modulus).1.is_zero() {
+            true => Ok(true),
+            false => self.inv(a).map(|_| false),
+        }
+    }
+
+    fn inv(&self, a: &Polynomial<F>) -> Result<Polynomial<F>, Split<F>> {
+        inverse_mod(a, &self.modulus)
+    }
+
+    fn mul_mod(&self, a: &Polynomial<F>, b: &Polynomial<F>) -> Polynomial<F> {
+        a.mul_mod(b, &self.modulus)
+    }
+
+    fn pow(&self, base: &Polynomial<F>, exp: &BigUint) -> Polynomial<F> {
+        base.pow_mod(exp, &self.modulus)
+    }
+}
+
+fn small_mod(n: &BigUint, l: T) -> T {
+    T::try_from(n % l).unwrap()
+}
+
+/// Inverse of a non-zero `a` modulo `m`, or their common monic factor if there is one.
+fn inverse_mod<F: Field + Inverse>(
+    a: &Polynomial<F>,
+    m: &Polynomial<F>,
+) -> Result<Polynomial<F>, Split<F>> {
+    let (g, s, _) = a.extended_gcd(m);
+    match g.degree() {
+        Some(0) => Ok(s.div_rem(m).1),
+        _ => Err(g),
+    }
+}
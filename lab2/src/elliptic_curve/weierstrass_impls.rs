@@ -0,0 +1,417 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use super::ec_point_impls::from_int;
+use super::{Ec, EcErrors, EcPoint, WeierstrassEc, WeierstrassPoint};
+use crate::{
+    T,
+    f2m::F2m,
+    traits::{Field, Inverse, Normal, Pow},
+};
+
+impl<T: Field + Inverse> WeierstrassEc<T> {
+    pub fn new(a1: T, a2: T, a3: T, a4: T, a6: T) -> Self {
+        Self { a1, a2, a3, a4, a6 }
+    }
+
+    /// Creates a curve, rejecting parameters for which it is singular.
+    pub fn try_new(a1: T, a2: T, a3: T, a4: T, a6: T) -> Result<Self, EcErrors> {
+        let ec = Self::new(a1, a2, a3, a4, a6);
+        match ec.is_singular() {
+            true => Err(EcErrors::SingularCurve),
+            false => Ok(ec),
+        }
+    }
+
+    /// Returns (b2, b4, b6, b8) as defined in Silverman, III.1.
+    fn b_invariants(&self) -> (T, T, T, T) {
+        let Self { a1, a2, a3, a4, a6 } = self.clone();
+        let n = |k| from_int(&a1, k);
+
+        let b2 = a1.clone().pow(2) + n(4) * a2.clone();
+        let b4 = n(2) * a4.clone() + a1.clone() * a3.clone();
+        let b6 = a3.clone().pow(2) + n(4) * a6.clone();
+        let b8 = a1.clone().pow(2) * a6.clone() + n(4) * a2.clone() * a6
+            - a1 * a3.clone() * a4.clone()
+            + a2 * a3.pow(2)
+            - a4.pow(2);
+        (b2, b4, b6, b8)
+    }
+
+    /// -b2^2 b8 - 8 b4^3 - 27 b6^2 + 9 b2 b4 b6
+    pub fn discriminant(&self) -> T {
+        let (b2, b4, b6, b8) = self.b_invariants();
+        let n = |k| from_int(&b2, k);
+
+        -(b2.clone().pow(2) * b8) - n(8) * b4.clone().pow(3) - n(27) * b6.clone().pow(2)
+            + n(9) * b2 * b4 * b6
+    }
+
+    pub fn is_singular(&self) -> bool {
+        let discriminant = self.discriminant();
+        discriminant == discriminant.zero()
+    }
+
+    /// c4^3 / discriminant with c4 = b2^2 - 24 b4, `None` for singular curves.
+    pub fn j_invariant(&self) -> Option<T> {
+        if self.is_singular() {
+            return None;
+        }
+        let (b2, b4, _, _) = self.b_invariants();
+        let c4 = b2.clone().pow(2) - from_int(&b2, 24) * b4;
+        Some(c4.pow(3) * self.discriminant().inv())
+    }
+
+    pub fn is_point_on_curve(&self, point: &WeierstrassPoint<T>) -> bool {
+        match point {
+            WeierstrassPoint::Infinity => true,
+            WeierstrassPoint::Point { x, y, ec } => {
+                let lhs = y.clone().pow(2)
+                    + ec.a1.clone() * x.clone() * y.clone()
+                    + ec.a3.clone() * y.clone();
+                let rhs = x.clone().pow(3)
+                    + ec.a2.clone() * x.clone().pow(2)
+                    + ec.a4.clone() * x.clone()
+                    + ec.a6.clone();
+                lhs == rhs
+            }
+        }
+    }
+
+    /// Curve obtained by the substitution x = u^2 x' + r, y = u^3 y' + s u^2 x' + t.
+    pub fn change_coordinates(&self, u: &T, r: &T, s: &T, t: &T) -> Self {
+        let Self { a1, a2, a3, a4, a6 } = self.clone();
+        let (r, s, t) = (r.clone(), s.clone(), t.clone());
+        let n = |k| from_int(&a1, k);
+        let u_inv = u.clone().inv();
+
+        let a1_new = (a1.clone() + n(2) * s.clone()) * u_inv.clone();
+        let a2_new = (a2.clone() - s.clone() * a1.clone() + n(3) * r.clone() - s.clone().pow(2))
+            * u_inv.clone().pow(2);
+        let a3_new =
+            (a3.clone() + r.clone() * a1.clone() + n(2) * t.clone()) * u_inv.clone().pow(3);
+        let a4_new = (a4.clone() - s.clone() * a3.clone() + n(2) * r.clone() * a2.clone()
+            - (t.clone() + r.clone() * s.clone()) * a1.clone()
+            + n(3) * r.clone().pow(2)
+            - n(2) * s * t.clone())
+            * u_inv.clone().pow(4);
+        let a6_new = (a6 + r.clone() * a4 + r.clone().pow(2) * a2 + r.clone().pow(3)
+            - t.clone() * a3
+            - t.clone().pow(2)
+            - r * t * a1)
+            * u_inv.pow(6);
+
+        Self::new(a1_new, a2_new, a3_new, a4_new, a6_new)
+    }
+
+    fn add_points(&self, (x1, y1): (T, T), (x2, y2): (T, T)) -> WeierstrassPoint<T> {
+        let denominator = (x2.clone() - x1.clone()).inv();
+        let lambda = (y2.clone() - y1.clone()) * denominator.clone();
+        let nu = (y1 * x2.clone() - y2 * x1.clone()) * denominator;
+        self.third_point(x1, x2, lambda, nu)
+    }
+
+    fn double_point(&self, (x, y): (T, T)) -> WeierstrassPoint<T> {
+        let n = |k| from_int(&x, k);
+        let denominator = n(2) * y.clone() + self.a1.clone() * x.clone() + self.a3.clone();
+        if denominator == x.zero() {
+            return WeierstrassPoint::Infinity;
+        }
+
+        let denominator = denominator.inv();
+        let lambda =
+            (n(3) * x.clone().pow(2) + n(2) * self.a2.clone() * x.clone() + self.a4.clone()
+                - self.a1.clone() * y.clone())
+                * denominator.clone();
+        let nu = (-x.clone().pow(3) + self.a4.clone() * x.clone() + n(2) * self.a6.clone()
+            - self.a3.clone() * y)
+            * denominator;
+        self.third_point(x.clone(), x, lambda, nu)
+    }
+
+    /// Negated third intersection of the line y = lambda x + nu with the curve.
+    fn third_point(&self, x1: T, x2: T, lambda: T, nu: T) -> WeierstrassPoint<T> {
+        let x =
+            lambda.clone().pow(2) + self.a1.clone() * lambda.clone() - self.a2.clone() - x1 - x2;
+        let y = -(lambda + self.a1.clone()) * x.clone() - nu - self.a3.clone();
+        WeierstrassPoint::Point {
+            x,
+            y,
+            ec: self.clone(),
+        }
+    }
+}
+
+impl<T: Field + Normal + Inverse> WeierstrassEc<T> {
+    /// Substitution (u, r, s, t) leading to y^2 = x^3 + ax + b,
+    /// `None` in characteristic 2 and 3 where it does not exist in general.
+    fn short_form_change(&self) -> Option<(T, T, T, T)> {
+        let n = |k| from_int(&self.a1, k);
+        let (zero, two, three) = (n(0), n(2), n(3));
+        if two == zero || three == zero {
+            return None;
+        }
+
+        let (b2, _, _, _) = self.b_invariants();
+        let r = -b2 * n(12).inv();
+        let s = -self.a1.clone() * two.clone().inv();
+        let t = -(self.a3.clone() + r.clone() * self.a1.clone()) * two.inv();
+        Some((n(1), r, s, t))
+    }
+
+    /// Isomorphic curve in short form, `None` in characteristic 2 and 3.
+    pub fn to_short(&self) -> Option<Ec<T>> {
+        let (u, r, s, t) = self.short_form_change()?;
+        let ec = self.change_coordinates(&u, &r, &s, &t);
+        Some(Ec::new(ec.a4, ec.a6))
+    }
+}
+
+impl<const M: T> WeierstrassEc<F2m<M>> {
+    /// Substitution (u, r, s, t) leading to y^2 + xy = x^3 + ax^2 + b,
+    /// `None` for supersingular curves (a1 = 0).
+    fn binary_form_change(&self) -> Option<(F2m<M>, F2m<M>, F2m<M>, F2m<M>)> {
+        if self.a1.is_zero() {
+            return None;
+        }
+
+        let a1_inv = self.a1.clone().inv();
+        let r = self.a3.clone() * a1_inv.clone();
+        let t = (self.a4.clone() + r.clone().pow(2)) * a1_inv;
+        Some((self.a1.clone(), r, self.a1.zero(), t))
+    }
+
+    /// Isomorphic curve in the form used by `Ec<F2m>`, `None` for supersingular curves.
+    pub fn to_binary(&self) -> Option<Ec<F2m<M>>> {
+        let (u, r, s, t) = self.binary_form_change()?;
+        let ec = self.change_coordinates(&u, &r, &s, &t);
+        Some(Ec::new(ec.a2, ec.a6))
+    }
+}
+
+impl<T: Field + Normal> From<Ec<T>> for WeierstrassEc<T> {
+    fn from(ec: Ec<T>) -> Self {
+        let zero = ec.a.zero();
+        Self {
+            a1: zero.clone(),
+            a2: zero.clone(),
+            a3: zero,
+            a4: ec.a.clone(),
+            a6: ec.b.clone(),
+        }
+    }
+}
+
+impl<const M: T> From<Ec<F2m<M>>> for WeierstrassEc<F2m<M>> {
+    fn from(ec: Ec<F2m<M>>) -> Self {
+        let zero = ec.a.zero();
+        Self {
+            a1: zero.one(),
+            a2: ec.a.clone(),
+            a3: zero.clone(),
+            a4: zero,
+            a6: ec.b.clone(),
+        }
+    }
+}
+
+impl<T> From<EcPoint<T>> for WeierstrassPoint<T>
+where
+    T: Field,
+    WeierstrassEc<T>: From<Ec<T>>,
+{
+    fn from(point: EcPoint<T>) -> Self {
+        match point {
+            EcPoint::Point { x, y, ec } => WeierstrassPoint::Point {
+                x,
+                y,
+                ec: ec.into(),
+            },
+            EcPoint::Infinity => WeierstrassPoint::Infinity,
+        }
+    }
+}
+
+impl<T: Field + Inverse> WeierstrassPoint<T> {
+    /// Creates a point, rejecting coordinates that do not satisfy the curve equation.
+    pub fn new(x: T, y: T, ec: WeierstrassEc<T>) -> Result<Self, EcErrors> {
+        let point = Self::Point { x, y, ec };
+        match point.is_on_curve() {
+            true => Ok(point),
+            false => Err(EcErrors::PointNotOnCurve),
+        }
+    }
+
+    /// Creates a point without checking the curve equation.
+    /// Only for coordinates that are already known to be valid.
+    pub fn new_unchecked(x: T, y: T, ec: WeierstrassEc<T>) -> Self {
+        Self::Point { x, y, ec }
+    }
+
+    pub fn infinity() -> Self {
+        Self::Infinity
+    }
+
+    /// Curve of the point, `None` for the point at infinity.
+    pub fn curve(&self) -> Option<&WeierstrassEc<T>> {
+        match self {
+            WeierstrassPoint::Point { ec, .. } => Some(ec),
+            WeierstrassPoint::Infinity => None,
+        }
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        match self {
+            Self::Infinity => true,
+            Self::Point { ec, .. } => ec.is_point_on_curve(self),
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        match self {
+            WeierstrassPoint::Infinity => WeierstrassPoint::Infinity,
+            WeierstrassPoint::Point { x, y, ec } => ec.double_point((x.clone(), y.clone())),
+        }
+    }
+
+    /// Adds two points, failing with `CurveMismatch` if they lie on different curves.
+    pub fn checked_add(self, other: Self) -> Result<Self, EcErrors> {
+        Ok(match (self, other) {
+            (WeierstrassPoint::Infinity, p) => p,
+            (p, WeierstrassPoint::Infinity) => p,
+            (
+                WeierstrassPoint::Point { x: x1, y: y1, ec },
+                WeierstrassPoint::Point {
+                    x: x2,
+                    y: y2,
+                    ec: ec2,
+                },
+            ) => {
+                if ec != ec2 {
+                    return Err(EcErrors::CurveMismatch);
+                }
+                if x1 != x2 {
+                    ec.add_points((x1, y1), (x2, y2))
+                } else if y1.clone() + y2 + ec.a1.clone() * x2 + ec.a3.clone() == x1.zero() {
+                    WeierstrassPoint::Infinity
+                } else {
+                    ec.double_point((x1, y1))
+                }
+            }
+        })
+    }
+
+    /// Image of the point under the substitution from `WeierstrassEc::change_coordinates`.
+    pub fn change_coordinates(&self, u: &T, r: &T, s: &T, t: &T) -> Self {
+        match self {
+            WeierstrassPoint::Infinity => WeierstrassPoint::Infinity,
+            WeierstrassPoint::Point { x, y, ec } => {
+                let u_inv = u.clone().inv();
+                let x_shifted = x.clone() - r.clone();
+                WeierstrassPoint::Point {
+                    x: x_shifted.clone() * u_inv.clone().pow(2),
+                    y: (y.clone() - s.clone() * x_shifted - t.clone()) * u_inv.pow(3),
+                    ec: ec.change_coordinates(u, r, s, t),
+                }
+            }
+        }
+    }
+}
+
+impl<T: Field + Normal + Inverse> WeierstrassPoint<T> {
+    /// Image of the point on `WeierstrassEc::to_short` of its curve.
+    pub fn to_short(&self) -> Option<EcPoint<T>> {
+        let WeierstrassPoint::Point { ec, .. } = self else {
+            return Some(EcPoint::Infinity);
+        };
+        let (u, r, s, t) = ec.short_form_change()?;
+        match self.change_coordinates(&u, &r, &s, &t) {
+            WeierstrassPoint::Point { x, y, ec } => Some(EcPoint::Point {
+                x,
+                y,
+                ec: Ec::new(ec.a4, ec.a6),
+            }),
+            WeierstrassPoint::Infinity => Some(EcPoint::Infinity),
+        }
+    }
+}
+
+impl<const M: T> WeierstrassPoint<F2m<M>> {
+    /// Image of the point on `WeierstrassEc::to_binary` of its curve.
+    pub fn to_binary(&self) -> Option<EcPoint<F2m<M>>> {
+        let WeierstrassPoint::Point { ec, .. } = self else {
+            return Some(EcPoint::Infinity);
+        };
+        let (u, r, s, t) = ec.binary_form_change()?;
+        match self.change_coordinates(&u, &r, &s, &t) {
+            WeierstrassPoint::Point { x, y, ec } => Some(EcPoint::Point {
+                x,
+                y,
+                ec: Ec::new(ec.a2, ec.a6),
+            }),
+            WeierstrassPoint::Infinity => Some(EcPoint::Infinity),
+        }
+    }
+}
+
+impl<T: Field + Inverse> Neg for WeierstrassPoint<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        match self {
+            WeierstrassPoint::Point { x, y, ec } => WeierstrassPoint::Point {
+                y: -y - ec.a1.clone() * x.clone() - ec.a3.clone(),
+                x,
+                ec,
+            },
+            catch_all => catch_all,
+        }
+    }
+}
+
+impl<T: Field + Inverse> Add for WeierstrassPoint<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<T: Field + Inverse> Sub for WeierstrassPoint<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Add::add(self, other.neg())
+    }
+}
+
+impl<T: Field + Inverse> Mul for WeierstrassPoint<T> {
+    type Output = Self;
+
+    /// The group operation, so that `Pow` gives scalar multiplication.
+    fn mul(self, other: Self) -> Self::Output {
+        Add::add(self, other)
+    }
+}
+
+impl<T: Field + Inverse> Pow for WeierstrassPoint<T> {
+    fn zero(&self) -> Self {
+        Self::Infinity
+    }
+    fn one(&self) -> Self {
+        Self::Infinity
+    }
+}
+
+impl<T: Field + Inverse> Mul<usize> for WeierstrassPoint<T> {
+    type Output = Self;
+
+    fn mul(self, other: usize) -> Self::Output {
+        self.pow(other as u128)
+    }
+}
+
+impl<T: Field + Inverse> Inverse for WeierstrassPoint<T> {
+    fn inv(self) -> Self {
+        self.neg()
+    }
+}
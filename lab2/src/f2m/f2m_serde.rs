@@ -79,8 +79,8 @@ impl<const M: T> Serialize for F2m<M> {
     {
         let mut state = serializer.serialize_struct("F2m", 3)?;
         state.serialize_field("M", &M)?;
-        state.serialize_field("Poly", &PackedPoly(&self.poly))?;
-        state.serialize_field("Modulo", &PackedPoly(&self.modulo))?;
+        state.serialize_field("Poly", &PackedPoly(&self.poly()))?;
+        state.serialize_field("Modulo", &PackedPoly(&self.get_modulo()))?;
         state.end()
     }
 }
@@ -146,7 +146,8 @@ impl<'de, const M: T> Deserialize<'de> for F2m<M> {
                     use base64::{Engine as _, engine::general_purpose};
                     general_purpose::STANDARD
                         .decode(s)
-                        .map_err(serde::de::Error::custom)?.to_vec()
+                        .map_err(serde::de::Error::custom)?
+                        .to_vec()
                 } else {
                     let num = s.parse::<u128>().map_err(serde::de::Error::custom)?;
                     num.to_le_bytes().to_vec()
@@ -181,10 +182,12 @@ impl<'de, const M: T> Deserialize<'de> for F2m<M> {
                     }
                 }
 
-                Ok(F2m {
-                    poly: poly.ok_or_else(|| serde::de::Error::missing_field("poly"))?,
-                    modulo: modulo.ok_or_else(|| serde::de::Error::missing_field("modulo"))?,
-                })
+                let poly = poly.ok_or_else(|| serde::de::Error::missing_field("poly"))?;
+                let modulo = modulo.ok_or_else(|| serde::de::Error::missing_field("modulo"))?;
+                if F2m::<M>::poly_degree(&modulo) != Some(M as usize) {
+                    return Err(serde::de::Error::custom("Modulo must have degree M"));
+                }
+                Ok(F2m::new(poly, modulo))
             }
         }
 
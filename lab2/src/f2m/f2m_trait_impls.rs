@@ -1,19 +1,27 @@
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Div, Mul, Neg, Rem, Shl, Sub};
+use std::str::FromStr;
+use std::sync::Arc;
 
+use num_bigint::BigUint;
+use rand::Rng;
+
+use super::limbs::{self, Modulus};
 use super::{Bits8, F2m, T};
-use crate::polynomials::Polynomial;
-use crate::traits::needed_impls::gcd;
-use crate::traits::{Inverse, Pow};
+use crate::polynomials::{ModulusErrors, ParseErrors, Polynomial, split_modulus};
+use crate::scalar::ConditionalSwap;
+use crate::traits::{FieldBytes, FiniteField, Inverse, Pow, Sqrt};
 
 impl<const M: T> F2m<M> {
+    const LIMBS: usize = (M as usize).div_ceil(64);
+
     pub fn new(poly: Polynomial<Bits8>, modulo: Polynomial<Bits8>) -> Self {
         let mod_deg = Self::poly_degree(&modulo).expect("Modulo must have a positive degree");
         assert_eq!(mod_deg, M as usize);
 
-        let mut result = Self { poly, modulo };
-        result.reduce();
-        result
+        let modulo = Arc::new(Modulus::new(limbs::from_bytes(&modulo.coefficients())));
+        Self::from_limbs(limbs::from_bytes(&poly.coefficients()), &modulo)
     }
 
     pub fn new_from_slice(poly: &[Bits8], modulo: &[Bits8]) -> Self {
@@ -23,32 +31,80 @@ impl<const M: T> F2m<M> {
         Self::new(poly, modulo)
     }
 
+    /// Like `new`, but refuses moduli that are not irreducible of degree M,
+    /// which do not give a field.
+    pub fn new_checked(
+        poly: Polynomial<Bits8>,
+        modulo: Polynomial<Bits8>,
+    ) -> Result<Self, ModulusErrors> {
+        if Self::poly_degree(&modulo) != Some(M as usize) {
+            return Err(ModulusErrors::WrongDegree);
+        }
+        if !modulo.is_irreducible() {
+            return Err(ModulusErrors::Reducible);
+        }
+        Ok(Self::new(poly, modulo))
+    }
+
+    /// Parses the notation of `Display`, which leaves out the modulus, over `modulo`
+    /// as in `new`.
+    pub fn parse_with_modulus(s: &str, modulo: &Polynomial<Bits8>) -> Result<Self, ParseErrors> {
+        let poly: Polynomial<Bits8> = s.parse()?;
+        if Self::poly_degree(&poly).is_some_and(|degree| degree >= M as usize) {
+            return Err(ParseErrors::DegreeTooLarge);
+        }
+        Ok(Self::new(poly, modulo.clone()))
+    }
+
+    /// Element over the trinomial or pentanomial from `Polynomial::sparse_irreducible`.
+    /// The search runs on every call, so further elements should reuse the modulus.
+    pub fn with_sparse_modulus(poly: Polynomial<Bits8>) -> Self {
+        let modulo = Polynomial::<Bits8>::sparse_irreducible(M as usize)
+            .expect("No irreducible trinomial or pentanomial of this degree");
+        Self::new(poly, modulo)
+    }
+
+    /// Element over a random irreducible modulus of degree M.
+    pub fn with_random_modulus<R: Rng + ?Sized>(poly: Polynomial<Bits8>, rng: &mut R) -> Self {
+        Self::new(
+            poly,
+            Polynomial::<Bits8>::random_irreducible(M as usize, rng),
+        )
+    }
+
     pub fn get_modulo(&self) -> Polynomial<Bits8> {
-        self.modulo.clone()
+        Polynomial::new(limbs::to_bytes(&self.modulo.limbs))
     }
 
     pub fn get_modulo_unreduced(&self) -> Self {
         Self {
-            poly: self.modulo.clone(),
+            limbs: self.modulo.limbs.clone(),
             modulo: self.modulo.clone(),
         }
     }
 
+    /// a reduced modulo `modulo`, in exactly `LIMBS` limbs.
+    fn from_limbs(limbs: Vec<u64>, modulo: &Arc<Modulus>) -> Self {
+        let mut limbs = modulo.rem(limbs);
+        limbs.resize(Self::LIMBS, 0);
+        Self {
+            limbs,
+            modulo: modulo.clone(),
+        }
+    }
+
+    /// Compares the moduli, by pointer first.
     #[inline]
     fn match_mods(lhs: &Self, rhs: &Self) -> bool {
-        lhs.modulo
-            .coefficients()
-            .iter()
-            .zip(rhs.modulo.coefficients().iter())
-            .all(|(a, b)| a == b)
+        Arc::ptr_eq(&lhs.modulo, &rhs.modulo) || lhs.modulo == rhs.modulo
     }
 
     #[inline]
     pub fn is_zero(&self) -> bool {
-        self.poly.is_zero()
+        self.limbs.iter().all(|&limb| limb == 0)
     }
 
-    fn poly_degree(poly: &Polynomial<Bits8>) -> Option<usize> {
+    pub(super) fn poly_degree(poly: &Polynomial<Bits8>) -> Option<usize> {
         let coef = poly.coefficients();
         match coef.len() {
             0 => None,
@@ -57,97 +113,188 @@ impl<const M: T> F2m<M> {
     }
 
     pub fn degree(&self) -> Option<usize> {
-        Self::poly_degree(&self.poly)
+        limbs::degree(&self.limbs)
     }
 
     #[inline]
     pub fn coefficients(&self) -> Vec<Bits8> {
-        self.poly.coefficients()
+        limbs::to_bytes(&self.limbs)
     }
 
-    // pub const fn create_unredeucable_polynomial() -> Self {
+    pub(super) fn poly(&self) -> Polynomial<Bits8> {
+        Polynomial::new(self.coefficients())
+    }
 
-    // }
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.coefficients().iter().map(|bits| bits.0).collect()
+    }
 
-    fn _get_bit(&self, index: usize) -> bool {
-        self.poly.get_bit(index)
+    /// Returns x^i reduced modulo the field polynomial.
+    fn monomial(&self, i: usize) -> Self {
+        let mut limbs = vec![0; i / 64 + 1];
+        limbs[i / 64] = 1 << (i % 64);
+        Self::from_limbs(limbs, &self.modulo)
     }
 
-    fn _get_bit_from_modulo(&self, index: usize) -> bool {
-        let coef = self.modulo.coefficients();
-        let byte_index = index / 8;
-        let bit_index = index % 8;
+    /// Absolute trace Tr(a) = a + a^2 + a^4 + ... + a^(2^(M - 1)), which is always 0 or 1.
+    pub fn trace(&self) -> bool {
+        let mut power = self.clone();
+        let mut result = self.clone();
+        for _ in 1..M {
+            power = power.clone() * power;
+            result = result + power.clone();
+        }
+        !result.is_zero()
+    }
+
+    /// Half-trace H(a) = a + a^4 + a^16 + ... + a^(2^(M - 1)), defined for odd M.
+    pub fn half_trace(&self) -> Self {
+        assert_eq!(M % 2, 1, "Half-trace is only defined for odd M");
+        let mut power = self.clone();
+        let mut result = self.clone();
+        for _ in 0..(M - 1) / 2 {
+            power = power.clone() * power;
+            power = power.clone() * power;
+            result = result + power.clone();
+        }
+        result
+    }
+
+    /// Solves z^2 + z = self. The other solution is z + 1.
+    /// Returns `None` when the trace of self is 1 and there is no solution.
+    pub fn solve_quadratic(&self) -> Option<Self> {
+        let z = if M % 2 == 1 {
+            self.half_trace()
+        } else {
+            // z = sum_{i=0}^{M-2} c_i * a^(2^i), where c_i = sum_{j=i+1}^{M-1} tau^(2^j)
+            // and tau is any element of trace 1
+            let tau = (0..M as usize)
+                .map(|i| self.monomial(i))
+                .find(|tau| tau.trace())
+                .expect("The trace is not identically zero");
+            let mut tau_powers = vec![tau];
+            for j in 1..M as usize {
+                tau_powers.push(tau_powers[j - 1].clone() * tau_powers[j - 1].clone());
+            }
+
+            let mut z = self.zero();
+            let mut c = self.zero();
+            let mut a_powers = vec![self.clone()];
+            for i in 1..M as usize - 1 {
+                a_powers.push(a_powers[i - 1].clone() * a_powers[i - 1].clone());
+            }
+            for i in (0..M as usize - 1).rev() {
+                c = c + tau_powers[i + 1].clone();
+                z = z + c.clone() * a_powers[i].clone();
+            }
+            z
+        };
 
-        if byte_index >= coef.len() {
-            false
+        if z.clone() * z.clone() + z.clone() == *self {
+            Some(z)
         } else {
-            (coef[byte_index].0 & (1 << bit_index)) != 0
+            None
         }
     }
+}
 
-    fn reduce(&mut self) {
-        self.poly = Self::div_rem_poly(&self.poly, &self.modulo).1;
+impl<const M: T> FiniteField for F2m<M> {
+    fn order(&self) -> BigUint {
+        BigUint::from(1_u8) << M
     }
 
-    fn div_rem_poly(
-        lhs: &Polynomial<Bits8>,
-        rhs: &Polynomial<Bits8>,
-    ) -> (Polynomial<Bits8>, Polynomial<Bits8>) {
-        lhs.div_rem(rhs)
+    /// Bit i of the index is the coefficient of x^i.
+    fn element_at(&self, index: T) -> Self {
+        Self::from_limbs(vec![index as u64, (index >> 64) as u64], &self.modulo)
     }
+}
 
-    pub fn to_vec(&self) -> Vec<u8> {
-        self.coefficients().iter().map(|bits| bits.0).collect()
+/// Coefficient of x^i is bit i of the big-endian number, as in SEC1.
+impl<const M: T> FieldBytes for F2m<M> {
+    fn byte_len(&self) -> usize {
+        (M as usize).div_ceil(8)
+    }
+
+    fn to_fixed_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_vec();
+        bytes.resize(self.byte_len(), 0);
+        bytes.reverse();
+        bytes
+    }
+
+    fn parse_fixed_bytes(&self, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != self.byte_len() {
+            return None;
+        }
+        let bytes: Vec<Bits8> = bytes.iter().rev().map(|byte| Bits8(*byte)).collect();
+        let limbs = limbs::from_bytes(&bytes);
+        match limbs::degree(&limbs) {
+            Some(degree) if degree >= M as usize => None,
+            _ => Some(Self::from_limbs(limbs, &self.modulo)),
+        }
+    }
+}
+
+/// Swaps the limbs, of which every element has `LIMBS`. The moduli stay, elements of
+/// one field have equal ones.
+impl<const M: T> ConditionalSwap for F2m<M> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        for (a, b) in self.limbs.iter_mut().zip(other.limbs.iter_mut()) {
+            let diff = (*a ^ *b) & mask;
+            *a ^= diff;
+            *b ^= diff;
+        }
     }
 }
 
 impl<const M: T> Pow for F2m<M> {
     fn zero(&self) -> Self {
-        Self::new(self.poly.zero(), self.modulo.clone())
+        Self::from_limbs(vec![0], &self.modulo)
     }
     fn one(&self) -> Self {
-        Self::new(self.poly.one(), self.modulo.clone())
+        Self::from_limbs(vec![1], &self.modulo)
+    }
+}
+
+/// Hashes the reduced limbs only, elements of different fields that share them
+/// are unequal anyway.
+impl<const M: T> Hash for F2m<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.limbs.hash(state);
     }
 }
 
+/// The polynomial, and with `{:#}` also ` mod ` and the modulus.
 impl<const M: T> Display for F2m<M> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.is_zero() {
-            return write!(f, "0");
+        match f.alternate() {
+            true => write!(f, "{} mod {}", self.poly(), self.get_modulo()),
+            false => write!(f, "{}", self.poly()),
         }
+    }
+}
 
-        let degree = self.degree().unwrap() + 1;
-        let coefficients = self.poly.coefficients();
+/// Reads the notation of `{:#}`, `<element> mod <modulus>`.
+impl<const M: T> FromStr for F2m<M> {
+    type Err = ParseErrors;
 
-        for i in (0..degree).rev() {
-            let coef = coefficients[i / 8].0 & (1 << (i % 8));
-            if coef == 0 {
-                continue;
-            }
-
-            if i != degree - 1 {
-                write!(f, " + ")?;
-            }
-
-            if i > 0 {
-                write!(f, "x")?;
-                if i > 1 {
-                    write!(f, "^{}", i)?;
-                }
-            } else if coef != 0 {
-                write!(f, "{}", coef)?;
-            }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (element, modulo): (_, Polynomial<Bits8>) = split_modulus(s)?;
+        if Self::poly_degree(&modulo) != Some(M as usize) {
+            return Err(ParseErrors::WrongModulusDegree);
         }
-        Ok(())
+        Self::parse_with_modulus(element, &modulo)
     }
 }
 
+/// Multiplication by x^shift.
 impl<const M: T> Shl<usize> for F2m<M> {
     type Output = Self;
 
-    fn shl(mut self, shift: usize) -> Self::Output {
-        self.poly = self.poly.clone() << shift;
-        self
+    fn shl(self, shift: usize) -> Self::Output {
+        let mut limbs = vec![0; self.limbs.len() + shift / 64 + 1];
+        limbs::xor_shifted(&mut limbs, &self.limbs, shift);
+        Self::from_limbs(limbs, &self.modulo)
     }
 }
 
@@ -162,12 +309,19 @@ impl<const M: T> Neg for F2m<M> {
 impl<const M: T> Add for F2m<M> {
     type Output = Self;
 
-    fn add(self, other: Self) -> Self {
+    fn add(mut self, other: Self) -> Self {
         assert!(Self::match_mods(&self, &other));
 
-        Self {
-            poly: self.poly + other.poly,
-            modulo: self.modulo,
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        self.limbs
+            .iter_mut()
+            .zip(&other.limbs)
+            .for_each(|(a, b)| *a ^= b);
+        match self.limbs.len() == Self::LIMBS {
+            true => self,
+            false => Self::from_limbs(self.limbs, &self.modulo),
         }
     }
 }
@@ -188,10 +342,15 @@ impl<const M: T> Mul for F2m<M> {
     fn mul(self, other: Self) -> Self::Output {
         assert!(Self::match_mods(&self, &other));
 
-        Self::new(self.poly * other.poly, other.modulo)
+        let product = match self.limbs == other.limbs {
+            true => limbs::square(&self.limbs),
+            false => limbs::mul(&self.limbs, &other.limbs),
+        };
+        Self::from_limbs(product, &self.modulo)
     }
 }
 
+/// Division of the representing polynomials, not of field elements.
 impl<const M: T> Div for F2m<M> {
     type Output = F2m<M>;
 
@@ -199,13 +358,12 @@ impl<const M: T> Div for F2m<M> {
         assert!(!other.is_zero(), "Division of F2m by zero");
         assert!(Self::match_mods(&self, &other));
 
-        Self {
-            poly: self.poly / other.poly,
-            modulo: self.modulo,
-        }
+        let quotient = self.poly() / other.poly();
+        Self::from_limbs(limbs::from_bytes(&quotient.coefficients()), &self.modulo)
     }
 }
 
+/// Remainder of the representing polynomials.
 impl<const M: T> Rem for F2m<M> {
     type Output = F2m<M>;
 
@@ -213,17 +371,26 @@ impl<const M: T> Rem for F2m<M> {
         assert!(!other.is_zero(), "Reminder of F2m by zero");
         assert!(Self::match_mods(&self, &other));
 
-        Self {
-            poly: self.poly % other.poly,
-            modulo: self.modulo,
-        }
+        let remainder = self.poly() % other.poly();
+        Self::from_limbs(limbs::from_bytes(&remainder.coefficients()), &self.modulo)
     }
 }
 
 impl<const M: T> Inverse for F2m<M> {
     fn inv(self) -> Self {
-        let (g, x, _) = gcd(self.poly, self.modulo.clone());
-        assert_eq!(g, g.one(), "Element is not invertible");
-        Self::new(x, self.modulo)
+        let inverse = limbs::inverse(&self.limbs, &self.modulo).expect("Element is not invertible");
+        Self::from_limbs(inverse, &self.modulo)
+    }
+}
+
+impl<const M: T> Sqrt for F2m<M> {
+    /// Squaring is a bijection in characteristic 2, so every element has exactly one
+    /// square root, the inverse Frobenius a^(2^(M - 1)).
+    fn sqrt(self) -> Option<Self> {
+        let mut result = self;
+        for _ in 1..M {
+            result = result.clone() * result;
+        }
+        Some(result)
     }
 }
@@ -0,0 +1,248 @@
+//! Polynomials over GF(2) as little-endian `u64` limbs, with bit i the coefficient of x^i.
+
+use super::Bits8;
+use crate::T;
+
+/// Moduli with at most this many terms below the leading one are reduced a word at a time.
+const SPARSE_TERMS: usize = 8;
+/// Operands shorter than this many limbs are multiplied by schoolbook, longer by Karatsuba.
+const KARATSUBA_LIMBS: usize = 8;
+
+/// Limbs of a polynomial given by little-endian bytes, at least one.
+pub(crate) fn from_bytes(bytes: &[Bits8]) -> Vec<u64> {
+    let mut limbs: Vec<u64> = bytes
+        .chunks(8)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |limb, (i, byte)| limb | (byte.0 as u64) << (8 * i))
+        })
+        .collect();
+    if limbs.is_empty() {
+        limbs.push(0);
+    }
+    limbs
+}
+
+/// Little-endian bytes without the zero bytes at the top, as in `Polynomial<Bits8>`.
+pub(crate) fn to_bytes(limbs: &[u64]) -> Vec<Bits8> {
+    let mut bytes: Vec<Bits8> = limbs
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .map(Bits8)
+        .collect();
+    while bytes.last() == Some(&Bits8(0)) {
+        bytes.pop();
+    }
+    bytes
+}
+
+pub(crate) fn degree(a: &[u64]) -> Option<usize> {
+    let top = a.iter().rposition(|&limb| limb != 0)?;
+    Some(64 * top + 63 - a[top].leading_zeros() as usize)
+}
+
+/// a ^= b * x^shift, with a long enough.
+pub(crate) fn xor_shifted(a: &mut [u64], b: &[u64], shift: usize) {
+    let (limbs, bits) = (shift / 64, shift % 64);
+    for (i, &limb) in b.iter().enumerate() {
+        a[i + limbs] ^= limb << bits;
+        if bits > 0 && limb >> (64 - bits) != 0 {
+            a[i + limbs + 1] ^= limb >> (64 - bits);
+        }
+    }
+}
+
+/// A modulus f = x^n + g with what its reductions need precomputed.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct Modulus {
+    pub(crate) limbs: Vec<u64>,
+    pub(crate) degree: usize,
+    /// Exponents of the terms of g, when g is sparse.
+    pub(crate) terms: Option<Vec<usize>>,
+}
+
+impl Modulus {
+    pub(crate) fn new(mut limbs: Vec<u64>) -> Self {
+        let degree = degree(&limbs).expect("Division by zero");
+        limbs.truncate(degree / 64 + 1);
+        let terms: Vec<usize> = (0..degree)
+            .filter(|&i| limbs[i / 64] >> (i % 64) & 1 == 1)
+            .collect();
+        Modulus {
+            limbs,
+            degree,
+            terms: (terms.len() <= SPARSE_TERMS).then_some(terms),
+        }
+    }
+
+    /// a modulo f, in degree / 64 + 1 limbs. A sparse g replaces the bits of a at or above
+    /// x^n a word at a time, otherwise they are cleared one by one with shifted copies of f.
+    pub(crate) fn rem(&self, mut a: Vec<u64>) -> Vec<u64> {
+        let n = self.degree;
+        let top = degree(&a).unwrap_or(0);
+        a.resize(a.len().max(n / 64 + 1), 0);
+        match &self.terms {
+            Some(terms) => {
+                for w in (n / 64..=top / 64).rev() {
+                    // Bit j of v stands for x^(start + j)
+                    let start = (64 * w).max(n);
+                    loop {
+                        let v = a[w] >> (start - 64 * w);
+                        if v == 0 {
+                            break;
+                        }
+                        a[w] ^= v << (start - 64 * w);
+                        for &t in terms {
+                            let shift = start - n + t;
+                            a[shift / 64] ^= v << (shift % 64);
+                            if !shift.is_multiple_of(64) && v >> (64 - shift % 64) != 0 {
+                                a[shift / 64 + 1] ^= v >> (64 - shift % 64);
+                            }
+                        }
+                    }
+                }
+            }
+            None => {
+                for i in (n..=top).rev() {
+                    if a[i / 64] >> (i % 64) & 1 == 1 {
+                        xor_shifted(&mut a, &self.limbs, i - n);
+                    }
+                }
+            }
+        }
+        a.truncate(n / 64 + 1);
+        a
+    }
+}
+
+/// a^2, which over GF(2) spreads the bits of a to the even positions.
+pub(crate) fn square(a: &[u64]) -> Vec<u64> {
+    fn spread(half: u64) -> u64 {
+        let mut x = half & 0xffff_ffff;
+        x = (x | x << 16) & 0x0000_ffff_0000_ffff;
+        x = (x | x << 8) & 0x00ff_00ff_00ff_00ff;
+        x = (x | x << 4) & 0x0f0f_0f0f_0f0f_0f0f;
+        x = (x | x << 2) & 0x3333_3333_3333_3333;
+        (x | x << 1) & 0x5555_5555_5555_5555
+    }
+    a.iter()
+        .flat_map(|&limb| [spread(limb), spread(limb >> 32)])
+        .collect()
+}
+
+/// Carry-less product a * b, by Karatsuba down to schoolbook on short operands.
+pub(crate) fn mul(a: &[u64], b: &[u64]) -> Vec<u64> {
+    if a.len().min(b.len()) < KARATSUBA_LIMBS {
+        return schoolbook(a, b);
+    }
+
+    // a = a0 + a1 x^(64 h), b likewise, and a * b = z0 + z1 x^(64 h) + z2 x^(128 h)
+    let h = a.len().max(b.len()).div_ceil(2);
+    let split = |c: &[u64]| {
+        let (low, high) = c.split_at(h.min(c.len()));
+        (low.to_vec(), high.to_vec())
+    };
+    let ((a0, a1), (b0, b1)) = (split(a), split(b));
+    let (z0, z2) = (mul(&a0, &b0), mul(&a1, &b1));
+    let sum = |low: &[u64], high: &[u64]| {
+        let mut sum = low.to_vec();
+        sum.iter_mut().zip(high).for_each(|(s, h)| *s ^= h);
+        sum
+    };
+    let z1 = mul(&sum(&a0, &a1), &sum(&b0, &b1));
+
+    let mut result = vec![0; 4 * h];
+    for (i, limb) in z0.iter().enumerate() {
+        result[i] ^= limb;
+        result[i + h] ^= limb;
+    }
+    for (i, limb) in z2.iter().enumerate() {
+        result[i + h] ^= limb;
+        result[i + 2 * h] ^= limb;
+    }
+    for (i, limb) in z1.iter().enumerate() {
+        result[i + h] ^= limb;
+    }
+    result.truncate(a.len() + b.len());
+    result
+}
+
+/// Word by word products, each a 4-bit window over a table of the multiples of a[i].
+fn schoolbook(a: &[u64], b: &[u64]) -> Vec<u64> {
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate().filter(|&(_, &x)| x != 0) {
+        let mut table = [0_u128; 16];
+        for j in 1..16 {
+            table[j] = (table[j >> 1] << 1) ^ ((j as u128 & 1) * x as u128);
+        }
+        for (j, &y) in b.iter().enumerate() {
+            let product = (0..64).step_by(4).rev().fold(0, |acc, shift| {
+                (acc << 4) ^ table[(y >> shift) as usize & 15]
+            });
+            result[i + j] ^= product as u64;
+            result[i + j + 1] ^= (product >> 64) as u64;
+        }
+    }
+    result
+}
+
+/// base^exp modulo f.
+pub(crate) fn pow(base: &[u64], exp: T, f: &Modulus) -> Vec<u64> {
+    let base = f.rem(base.to_vec());
+    (0..T::BITS - exp.leading_zeros())
+        .rev()
+        .fold(f.rem(vec![1]), |result, i| {
+            let result = f.rem(square(&result));
+            match exp >> i & 1 {
+                1 => f.rem(mul(&result, &base)),
+                _ => result,
+            }
+        })
+}
+
+/// Euclid's algorithm in place: subtracts a shifted copy of the polynomial of lower
+/// degree from the other until one of them is zero.
+pub(crate) fn gcd(mut a: Vec<u64>, mut b: Vec<u64>) -> Vec<u64> {
+    let length = a.len().max(b.len()) + 1;
+    a.resize(length, 0);
+    b.resize(length, 0);
+    let (mut da, mut db) = (degree(&a), degree(&b));
+    while let (Some(x), Some(y)) = (da, db) {
+        if x < y {
+            (a, b, da, db) = (b, a, db, da);
+            continue;
+        }
+        xor_shifted(&mut a, &b[..=y / 64], x - y);
+        da = degree(&a[..=x / 64]);
+    }
+    match da {
+        Some(_) => a,
+        None => b,
+    }
+}
+
+/// Inverse of a modulo f by the extended Euclidean algorithm, keeping a * g1 = u and
+/// a * g2 = v modulo f. `None` when a and f have a common factor.
+pub(crate) fn inverse(a: &[u64], f: &Modulus) -> Option<Vec<u64>> {
+    let length = f.limbs.len() + 1;
+    let (mut u, mut v) = (f.rem(a.to_vec()), f.limbs.clone());
+    let (mut g1, mut g2) = (vec![1], vec![0]);
+    for c in [&mut u, &mut v, &mut g1, &mut g2] {
+        c.resize(length, 0);
+    }
+    let (mut du, mut dv) = (degree(&u)?, f.degree);
+    while du > 0 {
+        if du < dv {
+            (u, v, g1, g2, du, dv) = (v, u, g2, g1, dv, du);
+        }
+        let shift = du - dv;
+        xor_shifted(&mut u, &v[..=dv / 64], shift);
+        if let Some(top) = degree(&g2) {
+            xor_shifted(&mut g1, &g2[..=top / 64], shift);
+        }
+        du = degree(&u)?;
+    }
+    Some(f.rem(g1))
+}
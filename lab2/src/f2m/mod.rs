@@ -1,19 +1,21 @@
-use super::{
-    polynomials::Polynomial,
-    traits::{Field, Pow},
-};
+use std::sync::Arc;
+
+use super::traits::Field;
 use crate::T;
 
 pub mod bit;
 mod f2m_serde;
 mod f2m_trait_impls;
+pub(crate) mod limbs;
 
 use bit::Bits8;
 
+/// Element of GF(2^M) = GF(2)[x] / (modulo), kept reduced in `M.div_ceil(64)` limbs.
+/// The modulus is shared by every element computed from this one.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct F2m<const M: T> {
-    poly: Polynomial<Bits8>,
-    modulo: Polynomial<Bits8>,
+    limbs: Vec<u64>,
+    modulo: Arc<limbs::Modulus>,
 }
 
 impl<const M: T> Field for F2m<M> {}
@@ -21,6 +23,10 @@ impl<const M: T> Field for F2m<M> {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        polynomials::{ModulusErrors, ParseErrors, Polynomial},
+        traits::{Inverse, Pow, Sqrt, check_shared_modulus},
+    };
 
     #[test]
     fn test_degree() {
@@ -55,6 +61,36 @@ mod tests {
         assert_eq!(format!("{}", f2m), "x^5 + x^3 + x^2 + 1");
     }
 
+    #[test]
+    fn test_parse() {
+        let s = "x^5 + x^3 + x^2 + 1";
+        let modulo = Polynomial::new(vec![Bits8(0b1111011)]);
+        let f2m: F2m<6> = F2m::parse_with_modulus(s, &modulo).unwrap();
+        assert_eq!(f2m.to_string(), s);
+        assert_eq!(
+            f2m,
+            F2m::new(Polynomial::new(vec![Bits8(0b101101)]), modulo.clone())
+        );
+        assert_eq!(
+            F2m::<6>::parse_with_modulus("x^6", &modulo),
+            Err(ParseErrors::DegreeTooLarge)
+        );
+        let zero = F2m::<6>::parse_with_modulus("0", &modulo).unwrap();
+        assert_eq!(zero.to_string(), "0");
+
+        let full = format!("{:#}", f2m);
+        assert_eq!(
+            full,
+            "x^5 + x^3 + x^2 + 1 mod x^6 + x^5 + x^4 + x^3 + x + 1"
+        );
+        assert_eq!(full.parse(), Ok(f2m));
+        assert_eq!(s.parse::<F2m<6>>(), Err(ParseErrors::MissingModulus));
+        assert_eq!(
+            "x mod x^3 + 1".parse::<F2m<6>>(),
+            Err(ParseErrors::WrongModulusDegree)
+        );
+    }
+
     #[test]
     fn test_creation() {
         const M: T = 3;
@@ -69,6 +105,27 @@ mod tests {
         assert_eq!(p1, expected);
     }
 
+    #[test]
+    fn test_checked_creation() {
+        let poly = Polynomial::new(vec![Bits8(0b101101)]);
+        let aes = Polynomial::new(vec![Bits8(0x1b), Bits8(1)]);
+        assert!(F2m::<8>::new_checked(poly.clone(), aes.clone()).is_ok());
+        assert_eq!(
+            F2m::<9>::new_checked(poly.clone(), aes),
+            Err(ModulusErrors::WrongDegree)
+        );
+        let reducible = Polynomial::new(vec![Bits8(0x1a), Bits8(1)]);
+        assert_eq!(
+            F2m::<8>::new_checked(poly.clone(), reducible),
+            Err(ModulusErrors::Reducible)
+        );
+
+        let sparse = F2m::<113>::with_sparse_modulus(poly.clone());
+        assert_eq!(format!("{}", sparse.get_modulo()), "x^113 + x^9 + 1");
+        let random = F2m::<100>::with_random_modulus(poly, &mut rand::rng());
+        assert!(random.get_modulo().is_irreducible());
+    }
+
     #[test]
     fn test_creation2() {
         const M: T = 3;
@@ -163,6 +220,15 @@ mod tests {
         assert_eq!(p1 * p2, expected);
     }
 
+    #[test]
+    fn test_shared_modulus() {
+        const M: T = 5;
+        let pk: Polynomial<Bits8> = Polynomial::new(vec![Bits8(0b100101)]);
+        let p1: F2m<M> = F2m::new(Polynomial::new(vec![Bits8(0b101)]), pk.clone());
+        let p2: F2m<M> = F2m::new(Polynomial::new(vec![Bits8(0b1011)]), pk);
+        check_shared_modulus(p1, p2, |p| &p.modulo);
+    }
+
     #[test]
     fn test_division() {
         const M: T = 5;
@@ -194,4 +260,64 @@ mod tests {
 
         assert_eq!(p1 % p2, expected);
     }
+
+    #[test]
+    fn test_limb_arithmetic() {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let naive = |a: &[u64], b: &[u64]| {
+            let mut result = vec![0; a.len() + b.len() + 1];
+            for i in (0..64 * b.len()).filter(|i| b[i / 64] >> (i % 64) & 1 == 1) {
+                limbs::xor_shifted(&mut result, a, i);
+            }
+            result.truncate(a.len() + b.len());
+            result
+        };
+        for (m, n) in [(1, 1), (3, 5), (8, 8), (13, 8), (40, 40), (49, 17), (64, 3)] {
+            let a: Vec<u64> = (0..m).map(|_| rng.random()).collect();
+            let b: Vec<u64> = (0..n).map(|_| rng.random()).collect();
+            assert_eq!(limbs::mul(&a, &b), naive(&a, &b));
+            assert_eq!(limbs::square(&a), naive(&a, &a));
+        }
+
+        // Against the byte-wise polynomial arithmetic, for a sparse and a dense modulus
+        check_against_polynomials::<163>(Polynomial::<Bits8>::sparse_irreducible(163).unwrap());
+        check_against_polynomials::<200>(Polynomial::<Bits8>::random_irreducible(200, &mut rng));
+    }
+
+    fn check_against_polynomials<const D: T>(modulo: Polynomial<Bits8>) {
+        use rand::Rng;
+        let mut rng = rand::rng();
+        let mut random = || {
+            let bytes: Vec<_> = (0..D / 8 + 1).map(|_| Bits8(rng.random())).collect();
+            Polynomial::new(bytes) % modulo.clone()
+        };
+        for _ in 0..20 {
+            let (a, b) = (random(), random());
+            let product = F2m::<D>::new(a.clone() * b.clone(), modulo.clone());
+            let (x, y) = (F2m::new(a, modulo.clone()), F2m::new(b, modulo.clone()));
+            assert_eq!(x.clone() * y, product);
+            if !x.is_zero() {
+                assert_eq!(x.clone() * x.inv(), product.one());
+            }
+        }
+    }
+
+    fn check_sqrt<const D: T>(modulo: &[Bits8]) {
+        let modulo = Polynomial::new(modulo.to_vec());
+        for i in 0..1_u16 << D {
+            let bytes = i.to_le_bytes().map(Bits8);
+            let a: F2m<D> = F2m::new(Polynomial::new(bytes.to_vec()), modulo.clone());
+            let root = a.clone().sqrt().expect("Every element of F2m is a square");
+
+            assert_eq!(root.clone() * root, a);
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        check_sqrt::<5>(&[Bits8(0b100101)]);
+        check_sqrt::<8>(&[Bits8(0b00011011), Bits8(1)]);
+        check_sqrt::<9>(&[Bits8(0b00010001), Bits8(0b10)]);
+    }
 }
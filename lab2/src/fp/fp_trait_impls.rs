@@ -1,9 +1,13 @@
 use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
+use num_bigint::BigUint;
+
 use super::{Fp, T};
-use crate::traits::needed_impls::gcd;
-use crate::traits::{Inverse, Pow, Sqrt};
+use crate::arithmetic::mul_mod;
+use crate::scalar::ConditionalSwap;
+use crate::traits::needed_impls::{gcd, tonelli_shanks};
+use crate::traits::{FieldBytes, FiniteField, Inverse, Parity, Pow, Sqrt};
 
 impl<const P: T> From<T> for Fp<P> {
     fn from(value: T) -> Self {
@@ -29,6 +33,26 @@ impl<const P: T> Fp<P> {
     pub fn get(&self) -> T {
         self.0
     }
+
+    /// Legendre symbol (self / P): 0 for zero, 1 for non-zero squares and -1 otherwise.
+    pub fn legendre(&self) -> i8 {
+        if self.0 == 0 {
+            0
+        } else if P == 2 || self.pow((P - 1) / 2) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+}
+
+impl<const P: T> ConditionalSwap for Fp<P> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let mask = (mask as T) << 64 | mask as T;
+        let diff = (self.0 ^ other.0) & mask;
+        self.0 ^= diff;
+        other.0 ^= diff;
+    }
 }
 
 impl<const P: T> Pow for Fp<P> {
@@ -42,12 +66,55 @@ impl<const P: T> Pow for Fp<P> {
 }
 
 impl<const P: T> Sqrt for Fp<P> {
+    /// Tonelli–Shanks, with a single exponentiation when P = 3 mod 4.
     fn sqrt(self) -> Option<Self> {
-        if self.pow((P - 1) / 2) != self.one() {
-            None
-        } else {
-            Some(self.pow((P + 1) / 4))
+        match self.legendre() {
+            0 => return Some(self),
+            -1 => return None,
+            _ if P == 2 => return Some(self),
+            _ if P % 4 == 3 => return Some(self.pow((P + 1) / 4)),
+            _ => {}
+        }
+
+        let s = (P - 1).trailing_zeros();
+        let t = (P - 1) >> s;
+        let z = Self::new(Self::NON_RESIDUE.expect("Every odd prime field has a non-residue"));
+
+        tonelli_shanks(self.pow(t.div_ceil(2)), self.pow(t), z.pow(t), s)
+    }
+}
+
+impl<const P: T> FiniteField for Fp<P> {
+    fn order(&self) -> BigUint {
+        BigUint::from(P)
+    }
+
+    fn element_at(&self, index: T) -> Self {
+        Self::new(index)
+    }
+}
+
+impl<const P: T> Parity for Fp<P> {
+    fn is_odd(&self) -> bool {
+        self.0 % 2 == 1
+    }
+}
+
+impl<const P: T> FieldBytes for Fp<P> {
+    fn byte_len(&self) -> usize {
+        (T::BITS - P.leading_zeros()).div_ceil(8) as usize
+    }
+
+    fn to_fixed_bytes(&self) -> Vec<u8> {
+        self.0.to_be_bytes()[(T::BITS / 8) as usize - self.byte_len()..].to_vec()
+    }
+
+    fn parse_fixed_bytes(&self, bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != self.byte_len() {
+            return None;
         }
+        let value = bytes.iter().fold(0, |acc, byte| (acc << 8) | *byte as T);
+        (value < P).then(|| Self::new(value))
     }
 }
 
@@ -85,7 +152,7 @@ impl<const P: T> Mul for Fp<P> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        Self::new(self.0 * other.0)
+        Self(mul_mod(self.0, other.0, P))
     }
 }
 
@@ -94,7 +161,7 @@ impl<const P: T> Div for Fp<P> {
 
     fn div(self, other: Self) -> Self {
         assert_ne!(other, self.zero(), "Division of Polynomial by zero");
-        self * dbg!(other.inv())
+        self * other.inv()
     }
 }
 
@@ -108,8 +175,8 @@ impl<const P: T> Rem for Fp<P> {
 
 impl<const P: T> Inverse for Fp<P> {
     fn inv(self) -> Self {
-        let (g, x, _) = dbg!(gcd(self.0 as i128, P as i128));
+        let (g, x, _) = gcd(self.0 as i128, P as i128);
         assert_eq!(g, 1, "Element is not invertible");
-        Self::new((x + P as i128) as u128)
+        Self::new(x.rem_euclid(P as i128) as T)
     }
 }
@@ -1,19 +1,39 @@
-use super::traits::{Field, Inverse, Normal, Pow, Sqrt};
-use crate::T;
+use super::traits::{Field, Normal, Pow};
 pub use crate::fp::fp_serde::deser;
+use crate::{T, arithmetic::pow_mod};
 
 mod fp_serde;
 mod fp_trait_impls;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Copy, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Hash, Copy, Clone)]
 pub struct Fp<const P: T>(T);
 
-impl<const P: T> Field for Fp<P> {}
+impl<const P: T> Fp<P> {
+    /// Smallest quadratic non-residue, searched for at compile time and once per P.
+    const NON_RESIDUE: Option<T> = {
+        let mut z = 2;
+        while z < P && pow_mod(z, (P - 1) / 2, P) != P - 1 {
+            z += 1;
+        }
+        if z < P { Some(z) } else { None }
+    };
+}
+
+impl<const P: T> Field for Fp<P> {
+    /// z^((P - 1) / 2^log_order) for a non-residue z, whose 2^(log_order - 1)-th power is -1.
+    fn root_of_unity(&self, log_order: u32) -> Option<Self> {
+        if log_order > (P - 1).trailing_zeros() {
+            return None;
+        }
+        Some(Self::new(Self::NON_RESIDUE?).pow((P - 1) >> log_order))
+    }
+}
 impl<const P: T> Normal for Fp<P> {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::traits::{Inverse, Sqrt};
     const P: T = 19;
 
     #[test]
@@ -99,11 +119,85 @@ mod tests {
     fn test_sqrt() {
         for i in 0..P {
             let expected: Fp<P> = Fp::new(i);
-            if expected.pow((P - 1) / 2) == expected.one() {
+            if expected.pow((P - 1) / 2) == expected.one() || i == 0 {
                 assert_eq!(expected.sqrt().unwrap().pow(2), expected)
             } else {
                 assert_eq!(expected.sqrt(), None);
             }
         }
     }
+
+    fn check_sqrt<const Q: T>() {
+        let squares: Vec<Fp<Q>> = (0..Q).map(|i| Fp::new(i).pow(2)).collect();
+        for i in 0..Q {
+            let a: Fp<Q> = Fp::new(i);
+            match a.sqrt() {
+                Some(root) => assert_eq!(root * root, a, "wrong root of {} mod {}", i, Q),
+                None => assert!(!squares.contains(&a), "missed root of {} mod {}", i, Q),
+            }
+            assert_eq!(a.legendre() == 1, i != 0 && squares.contains(&a));
+        }
+    }
+
+    #[test]
+    fn test_sqrt_p_3_mod_4() {
+        check_sqrt::<3>();
+        check_sqrt::<23>();
+        check_sqrt::<103>();
+    }
+
+    #[test]
+    fn test_sqrt_p_1_mod_4() {
+        check_sqrt::<13>();
+        check_sqrt::<17>();
+        check_sqrt::<97>();
+        check_sqrt::<257>();
+    }
+
+    #[test]
+    fn test_sqrt_characteristic_2() {
+        // Every element of F_2 is its own square root
+        check_sqrt::<2>();
+        assert_eq!(Fp::<2>::new(1).sqrt(), Some(Fp::new(1)));
+        assert_eq!(Fp::<2>::new(0).sqrt(), Some(Fp::new(0)));
+    }
+
+    #[test]
+    fn test_sqrt_65537() {
+        const Q: T = 65537;
+        for i in 0..Q {
+            let a: Fp<Q> = Fp::new(i);
+            match a.sqrt() {
+                Some(root) => assert_eq!(root * root, a),
+                None => assert_eq!(a.legendre(), -1),
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        const Q: T = (1 << 127) - 1;
+        let minus_one: Fp<Q> = Fp::new(Q - 1);
+        let a: Fp<Q> = Fp::new(0x7edcba9876543210fedcba9876543210);
+
+        assert_eq!(minus_one * minus_one, minus_one.one());
+        assert_eq!(a * a.inv(), a.one());
+        assert_eq!(a / a, a.one());
+        assert_eq!(a.pow(Q - 1), a.one());
+        // 2^127 = 1 mod Q
+        assert_eq!(Fp::<Q>::new(1 << 126) * Fp::new(2), a.one());
+        assert_eq!((a * a).sqrt().map(|s| s * s), Some(a * a));
+    }
+
+    #[test]
+    fn test_modulus_above_64_bits() {
+        const Q: T = (1 << 89) - 1;
+        let a: Fp<Q> = Fp::new((1 << 88) + 12345);
+        let b: Fp<Q> = Fp::new((1 << 80) + 678910);
+
+        assert_eq!(a * b, b * a);
+        assert_eq!((a * b) / b, a);
+        assert_eq!(a.pow(Q), a);
+        assert_eq!(Fp::<Q>::new(2).pow(89), a.one());
+    }
 }
@@ -0,0 +1,345 @@
+use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use num_bigint::BigUint;
+
+use super::{FpMont, MontgomeryParams, limbs};
+use crate::T;
+use crate::scalar::ConditionalSwap;
+use crate::traits::needed_impls::tonelli_shanks;
+use crate::traits::{FieldBytes, FiniteField, Inverse, Parity, Pow, Sqrt};
+
+impl<C: MontgomeryParams<N>, const N: usize> From<T> for FpMont<C, N> {
+    fn from(value: T) -> Self {
+        FpMont::new(value)
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> FpMont<C, N> {
+    pub fn new(number: T) -> Self {
+        let words = [(number >> 64) as u64, number as u64];
+        Self::from_be_words(&words)
+    }
+
+    /// Creates an element from little-endian limbs, reducing them modulo P.
+    pub fn from_limbs(limbs: [u64; N]) -> Self {
+        Self::from_montgomery(limbs::mont_mul(&limbs, &C::R2, &C::MODULUS, C::INV))
+    }
+
+    /// Creates an element from a big-endian byte string of any length, reducing it modulo P.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        let mut words = vec![0; bytes.len().div_ceil(8)];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            words[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        words.reverse();
+        Self::from_be_words(&words)
+    }
+
+    /// Horner evaluation in base 2^64.
+    fn from_be_words(words: &[u64]) -> Self {
+        let mut base = [0; N];
+        if N == 1 {
+            base = C::R;
+        } else {
+            base[1] = 1;
+        }
+        let base = Self::from_limbs(base);
+
+        words
+            .iter()
+            .fold(Self::from_montgomery([0; N]), |acc, word| {
+                let mut limb = [0; N];
+                limb[0] = *word;
+                acc * base + Self::from_limbs(limb)
+            })
+    }
+
+    #[inline(always)]
+    const fn from_montgomery(limbs: [u64; N]) -> Self {
+        Self(limbs, PhantomData)
+    }
+
+    /// Returns the canonical (non Montgomery) little-endian limbs of the element.
+    pub fn to_limbs(&self) -> [u64; N] {
+        let mut one = [0; N];
+        one[0] = 1;
+        limbs::mont_mul(&self.0, &one, &C::MODULUS, C::INV)
+    }
+
+    /// Returns the canonical big-endian bytes of the element, padded to the byte length of P.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        let len = Self::modulus_bits().div_ceil(8) as usize;
+        let mut bytes: Vec<u8> = self
+            .to_limbs()
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect();
+        bytes.truncate(len);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the value of the element, panics if it does not fit in `T`.
+    pub fn get(&self) -> T {
+        let limbs = self.to_limbs();
+        assert!(
+            limbs.iter().skip(2).all(|l| *l == 0),
+            "Element does not fit in T"
+        );
+        limbs
+            .iter()
+            .take(2)
+            .rev()
+            .fold(0, |acc, l| (acc << 64) | *l as T)
+    }
+
+    pub fn modulus() -> [u64; N] {
+        C::MODULUS
+    }
+
+    pub fn modulus_bits() -> u32 {
+        limbs::bits(&C::MODULUS)
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        limbs::is_zero(&self.0)
+    }
+
+    /// Legendre symbol (self / P): 0 for zero, 1 for non-zero squares and -1 otherwise.
+    pub fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            0
+        } else if self.pow_limbs(&limbs::shr1(&C::MODULUS)) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Exponentiation by squaring with a little-endian multi-limb exponent.
+    pub fn pow_limbs(self, exp: &[u64; N]) -> Self {
+        let mut result = self.one();
+        for i in (0..limbs::bits(exp)).rev() {
+            result = result * result;
+            if limbs::bit(exp, i) {
+                result = result * self;
+            }
+        }
+        result
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Clone for FpMont<C, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Copy for FpMont<C, N> {}
+
+impl<C: MontgomeryParams<N>, const N: usize> PartialEq for FpMont<C, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Eq for FpMont<C, N> {}
+
+impl<C: MontgomeryParams<N>, const N: usize> Hash for FpMont<C, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> ConditionalSwap for FpMont<C, N> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        for (a, b) in self.0.iter_mut().zip(other.0.iter_mut()) {
+            let diff = (*a ^ *b) & mask;
+            *a ^= diff;
+            *b ^= diff;
+        }
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Pow for FpMont<C, N> {
+    fn zero(&self) -> Self {
+        Self::from_montgomery([0; N])
+    }
+
+    fn one(&self) -> Self {
+        Self::from_montgomery(C::R)
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Sqrt for FpMont<C, N> {
+    /// Tonelli–Shanks, with a single exponentiation when P = 3 mod 4.
+    fn sqrt(self) -> Option<Self> {
+        match self.legendre() {
+            0 => return Some(self),
+            -1 => return None,
+            _ => {}
+        }
+
+        let mut one = [0; N];
+        one[0] = 1;
+        if C::MODULUS[0] % 4 == 3 {
+            // (P + 1) / 4 = (P >> 2) + 1
+            let exp = limbs::add(&limbs::shr(&C::MODULUS, 2), &one).0;
+            return Some(self.pow_limbs(&exp));
+        }
+
+        let p_minus_one = limbs::sub(&C::MODULUS, &one).0;
+        let s = limbs::trailing_zeros(&p_minus_one);
+        let t = limbs::shr(&p_minus_one, s);
+        let z =
+            Self::from_montgomery(C::NON_RESIDUE.expect("Every odd prime field has a non-residue"));
+
+        // t is odd so (t + 1) / 2 = (t >> 1) + 1
+        let half = limbs::add(&limbs::shr1(&t), &one).0;
+        tonelli_shanks(
+            self.pow_limbs(&half),
+            self.pow_limbs(&t),
+            z.pow_limbs(&t),
+            s,
+        )
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> FiniteField for FpMont<C, N> {
+    fn order(&self) -> BigUint {
+        let bytes: Vec<u8> = C::MODULUS.iter().flat_map(|l| l.to_le_bytes()).collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    fn element_at(&self, index: T) -> Self {
+        Self::new(index)
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Parity for FpMont<C, N> {
+    fn is_odd(&self) -> bool {
+        self.to_limbs()[0] % 2 == 1
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> FieldBytes for FpMont<C, N> {
+    fn byte_len(&self) -> usize {
+        Self::modulus_bits().div_ceil(8) as usize
+    }
+
+    fn to_fixed_bytes(&self) -> Vec<u8> {
+        self.to_be_bytes()
+    }
+
+    fn parse_fixed_bytes(&self, bytes: &[u8]) -> Option<Self> {
+        // `from_be_bytes` reduces, so values of at least P do not survive the round trip
+        let element = Self::from_be_bytes(bytes);
+        (element.to_be_bytes() == bytes).then_some(element)
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Display for FpMont<C, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes: Vec<u8> = self
+            .to_limbs()
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect();
+        write!(f, "{}", BigUint::from_bytes_le(&bytes))
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> std::fmt::UpperHex for FpMont<C, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes: Vec<u8> = self
+            .to_limbs()
+            .iter()
+            .flat_map(|l| l.to_le_bytes())
+            .collect();
+        write!(f, "{:X}", BigUint::from_bytes_le(&bytes))
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Debug for FpMont<C, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FpMont({})", self)
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Neg for FpMont<C, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        if self.is_zero() {
+            self
+        } else {
+            Self::from_montgomery(limbs::sub(&C::MODULUS, &self.0).0)
+        }
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Add for FpMont<C, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (sum, carry) = limbs::add(&self.0, &other.0);
+        if carry == 1 || limbs::geq(&sum, &C::MODULUS) {
+            Self::from_montgomery(limbs::sub(&sum, &C::MODULUS).0)
+        } else {
+            Self::from_montgomery(sum)
+        }
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Sub for FpMont<C, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        let (difference, borrow) = limbs::sub(&self.0, &other.0);
+        if borrow == 1 {
+            Self::from_montgomery(limbs::add(&difference, &C::MODULUS).0)
+        } else {
+            Self::from_montgomery(difference)
+        }
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Mul for FpMont<C, N> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_montgomery(limbs::mont_mul(&self.0, &other.0, &C::MODULUS, C::INV))
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Div for FpMont<C, N> {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        assert!(!other.is_zero(), "Division of FpMont by zero");
+        self * other.inv()
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Rem for FpMont<C, N> {
+    type Output = Self;
+
+    /// Remainder of the canonical representatives, same as for `Fp`.
+    fn rem(self, other: Self) -> Self {
+        Self::from_limbs(limbs::rem(&self.to_limbs(), &other.to_limbs()))
+    }
+}
+
+impl<C: MontgomeryParams<N>, const N: usize> Inverse for FpMont<C, N> {
+    /// Inverse by Fermat's little theorem, a^-1 = a^(P - 2).
+    fn inv(self) -> Self {
+        assert!(!self.is_zero(), "Element is not invertible");
+        let mut two = [0; N];
+        two[0] = 2;
+        self.pow_limbs(&limbs::sub(&C::MODULUS, &two).0)
+    }
+}
@@ -0,0 +1,294 @@
+//! Fixed width arithmetic on little-endian `u64` limbs.
+//! Everything here is `const fn` so the Montgomery constants can be computed at compile time.
+
+/// Returns `a + b + carry` and the new carry.
+#[inline(always)]
+pub(super) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Returns `a - b - borrow` and the new borrow (0 or 1).
+#[inline(always)]
+pub(super) const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (t as u64, (t >> 127) as u64)
+}
+
+/// Returns `a + b * c + carry` and the new carry.
+#[inline(always)]
+pub(super) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+#[inline(always)]
+pub(super) const fn add<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut result = [0; N];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < N {
+        (result[i], carry) = adc(a[i], b[i], carry);
+        i += 1;
+    }
+    (result, carry)
+}
+
+#[inline(always)]
+pub(super) const fn sub<const N: usize>(a: &[u64; N], b: &[u64; N]) -> ([u64; N], u64) {
+    let mut result = [0; N];
+    let mut borrow = 0;
+    let mut i = 0;
+    while i < N {
+        (result[i], borrow) = sbb(a[i], b[i], borrow);
+        i += 1;
+    }
+    (result, borrow)
+}
+
+/// Returns `a >= b`.
+#[inline(always)]
+pub(super) const fn geq<const N: usize>(a: &[u64; N], b: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+#[inline(always)]
+pub(super) const fn is_zero<const N: usize>(a: &[u64; N]) -> bool {
+    let mut i = 0;
+    while i < N {
+        if a[i] != 0 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+pub(super) const fn shr1<const N: usize>(a: &[u64; N]) -> [u64; N] {
+    let mut result = [0; N];
+    let mut i = 0;
+    while i < N {
+        result[i] = a[i] >> 1;
+        if i + 1 < N {
+            result[i] |= a[i + 1] << 63;
+        }
+        i += 1;
+    }
+    result
+}
+
+pub(super) const fn shr<const N: usize>(a: &[u64; N], shift: u32) -> [u64; N] {
+    let mut result = *a;
+    let mut i = 0;
+    while i < shift {
+        result = shr1(&result);
+        i += 1;
+    }
+    result
+}
+
+pub(super) const fn trailing_zeros<const N: usize>(a: &[u64; N]) -> u32 {
+    let mut i = 0;
+    while i < N {
+        if a[i] != 0 {
+            return 64 * i as u32 + a[i].trailing_zeros();
+        }
+        i += 1;
+    }
+    64 * N as u32
+}
+
+/// Number of significant bits.
+pub(super) const fn bits<const N: usize>(a: &[u64; N]) -> u32 {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if a[i] != 0 {
+            return 64 * i as u32 + 64 - a[i].leading_zeros();
+        }
+    }
+    0
+}
+
+#[inline(always)]
+pub(super) const fn bit<const N: usize>(a: &[u64; N], index: u32) -> bool {
+    (a[index as usize / 64] >> (index % 64)) & 1 == 1
+}
+
+/// Returns `a + b mod m` for `a, b < m`.
+const fn add_mod<const N: usize>(a: &[u64; N], b: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    let (sum, carry) = add(a, b);
+    if carry == 1 || geq(&sum, m) {
+        sub(&sum, m).0
+    } else {
+        sum
+    }
+}
+
+/// Returns `2a mod m` for `a < m`.
+const fn double_mod<const N: usize>(a: &[u64; N], m: &[u64; N]) -> [u64; N] {
+    add_mod(a, a, m)
+}
+
+/// Returns `-m^-1 mod 2^64` using Newton iteration (each step doubles the number of correct bits).
+pub(super) const fn neg_inv(m0: u64) -> u64 {
+    let mut inv: u64 = 1;
+    let mut i = 0;
+    while i < 6 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(m0.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv.wrapping_neg()
+}
+
+/// Returns `R mod m` where `R = 2^(64 * N)`.
+pub(super) const fn r<const N: usize>(m: &[u64; N]) -> [u64; N] {
+    let mut result = [0; N];
+    result[0] = 1;
+    let mut i = 0;
+    while i < 64 * N {
+        result = double_mod(&result, m);
+        i += 1;
+    }
+    result
+}
+
+/// Returns `R^2 mod m` where `R = 2^(64 * N)`.
+pub(super) const fn r2<const N: usize>(m: &[u64; N]) -> [u64; N] {
+    let mut result = r(m);
+    let mut i = 0;
+    while i < 64 * N {
+        result = double_mod(&result, m);
+        i += 1;
+    }
+    result
+}
+
+/// Montgomery multiplication (CIOS), returns `a * b * R^-1 mod m`.
+/// Requires `a * b < m * R`, which holds whenever one operand is reduced.
+#[inline(always)]
+pub(super) const fn mont_mul<const N: usize>(
+    a: &[u64; N],
+    b: &[u64; N],
+    m: &[u64; N],
+    inv: u64,
+) -> [u64; N] {
+    let mut t = [0; N];
+    let mut t_n = 0;
+    let mut i = 0;
+    while i < N {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < N {
+            (t[j], carry) = mac(t[j], a[j], b[i], carry);
+            j += 1;
+        }
+        let (sum, t_n1) = adc(t_n, carry, 0);
+        t_n = sum;
+
+        let k = t[0].wrapping_mul(inv);
+        let (_, mut carry) = mac(t[0], k, m[0], 0);
+        let mut j = 1;
+        while j < N {
+            (t[j - 1], carry) = mac(t[j], k, m[j], carry);
+            j += 1;
+        }
+        let (sum, carry) = adc(t_n, carry, 0);
+        t[N - 1] = sum;
+        t_n = t_n1 + carry;
+        i += 1;
+    }
+
+    if t_n != 0 || geq(&t, m) {
+        sub(&t, m).0
+    } else {
+        t
+    }
+}
+
+/// Montgomery form of the smallest quadratic non-residue modulo the prime `m`, found by
+/// Euler's criterion. `None` when there is none, which among primes happens only for 2.
+pub(super) const fn non_residue<const N: usize>(m: &[u64; N], inv: u64) -> Option<[u64; N]> {
+    let one = r(m);
+    let minus_one = sub(m, &one).0;
+    let exp = shr1(m);
+    let mut z = double_mod(&one, m);
+    // z runs through 2, 3, ... and wraps to zero at m
+    while !is_zero(&z) {
+        let mut acc = one;
+        let mut i = bits(&exp);
+        while i > 0 {
+            i -= 1;
+            acc = mont_mul(&acc, &acc, m, inv);
+            if bit(&exp, i) {
+                acc = mont_mul(&acc, &z, m, inv);
+            }
+        }
+        if is_zero(&sub(&acc, &minus_one).0) {
+            return Some(z);
+        }
+        z = add_mod(&z, &one, m);
+    }
+    None
+}
+
+/// Integer remainder `a mod b` by binary long division.
+pub(super) fn rem<const N: usize>(a: &[u64; N], b: &[u64; N]) -> [u64; N] {
+    assert!(!is_zero(b), "Reminder by zero");
+    let mut result = [0; N];
+    let mut i = bits(a);
+    while i > 0 {
+        i -= 1;
+        let (mut shifted, overflow) = add(&result, &result);
+        shifted[0] |= bit(a, i) as u64;
+        result = if overflow == 1 || geq(&shifted, b) {
+            sub(&shifted, b).0
+        } else {
+            shifted
+        };
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neg_inv() {
+        for m0 in [1_u64, 3, 19, 0xffffffff00000001, u64::MAX] {
+            assert_eq!(m0.wrapping_mul(neg_inv(m0)), u64::MAX);
+        }
+    }
+
+    #[test]
+    fn test_r2() {
+        let m = [19];
+        assert_eq!(r(&m), [((1_u128 << 64) % 19) as u64]);
+        assert_eq!(r2(&m), [(((1_u128 << 64) % 19).pow(2) % 19) as u64]);
+    }
+
+    #[test]
+    fn test_non_residue() {
+        // 2 is a residue modulo 17 and 3 is not
+        let m = [17];
+        let three = mont_mul(&[3], &r2(&m), &m, neg_inv(17));
+        assert_eq!(non_residue(&m, neg_inv(17)), Some(three));
+        assert_eq!(non_residue(&[2], neg_inv(2)), None);
+    }
+
+    #[test]
+    fn test_rem() {
+        let a = [5, 7];
+        let b = [11, 0];
+        let expected = (((7_u128 << 64) + 5) % 11) as u64;
+        assert_eq!(rem(&a, &b), [expected, 0]);
+    }
+}
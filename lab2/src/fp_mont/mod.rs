@@ -0,0 +1,250 @@
+use std::marker::PhantomData;
+
+use super::traits::{Field, Normal};
+
+mod fp_mont_trait_impls;
+mod limbs;
+
+/// Parameters of a prime field whose modulus spans `N` 64-bit limbs.
+///
+/// Only `MODULUS` has to be provided, the Montgomery constants are computed at compile time.
+/// The modulus must be an odd prime.
+///
+/// ```
+/// use lab2::fp_mont::{FpMont, MontgomeryParams};
+///
+/// #[derive(Debug)]
+/// struct P19;
+/// impl MontgomeryParams<1> for P19 {
+///     const MODULUS: [u64; 1] = [19];
+/// }
+///
+/// let a: FpMont<P19, 1> = FpMont::new(17);
+/// assert_eq!(a * FpMont::new(18), FpMont::new(2));
+/// ```
+pub trait MontgomeryParams<const N: usize>: std::fmt::Debug + 'static {
+    /// Little-endian limbs of the modulus P.
+    const MODULUS: [u64; N];
+    /// -P^-1 mod 2^64
+    const INV: u64 = limbs::neg_inv(Self::MODULUS[0]);
+    /// R mod P where R = 2^(64 * N), which is the Montgomery form of one.
+    const R: [u64; N] = limbs::r(&Self::MODULUS);
+    /// R^2 mod P, used to move values into Montgomery form.
+    const R2: [u64; N] = limbs::r2(&Self::MODULUS);
+    /// Montgomery form of the smallest quadratic non-residue, computed once per P.
+    const NON_RESIDUE: Option<[u64; N]> = limbs::non_residue(&Self::MODULUS, Self::INV);
+}
+
+/// Element of the prime field described by `C`, kept in Montgomery form.
+/// Unlike `Fp` it works for moduli of any size and never allocates during arithmetic.
+pub struct FpMont<C: MontgomeryParams<N>, const N: usize>([u64; N], PhantomData<C>);
+
+impl<C: MontgomeryParams<N>, const N: usize> Field for FpMont<C, N> {}
+impl<C: MontgomeryParams<N>, const N: usize> Normal for FpMont<C, N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        T,
+        elliptic_curve::{Ec, EcPoint},
+        fp::Fp,
+        polynomials::Polynomial,
+        traits::{Inverse, Pow, Sqrt},
+    };
+
+    const P: T = 19;
+
+    #[derive(Debug)]
+    struct P19;
+    impl MontgomeryParams<1> for P19 {
+        const MODULUS: [u64; 1] = [19];
+    }
+
+    #[derive(Debug)]
+    struct Goldilocks;
+    impl MontgomeryParams<1> for Goldilocks {
+        const MODULUS: [u64; 1] = [0xffffffff00000001];
+    }
+
+    #[derive(Debug)]
+    struct Mersenne127;
+    impl MontgomeryParams<2> for Mersenne127 {
+        const MODULUS: [u64; 2] = [u64::MAX, u64::MAX >> 1];
+    }
+
+    /// p = 2^256 - 2^32 - 977
+    #[derive(Debug)]
+    struct Secp256k1;
+    impl MontgomeryParams<4> for Secp256k1 {
+        const MODULUS: [u64; 4] = [
+            0xfffffffefffffc2f,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+            0xffffffffffffffff,
+        ];
+    }
+
+    /// p = 2^256 - 2^224 + 2^192 + 2^96 - 1
+    #[derive(Debug)]
+    struct P256;
+    impl MontgomeryParams<4> for P256 {
+        const MODULUS: [u64; 4] = [
+            0xffffffffffffffff,
+            0x00000000ffffffff,
+            0x0000000000000000,
+            0xffffffff00000001,
+        ];
+    }
+
+    type F19 = FpMont<P19, 1>;
+
+    #[test]
+    fn test_creation() {
+        let one: F19 = FpMont::new(100);
+
+        assert_eq!(one, FpMont::new(5));
+        assert_eq!(one.to_limbs(), [5]);
+    }
+
+    #[test]
+    fn test_display() {
+        let one: F19 = FpMont::new(17);
+
+        assert_eq!(format!("{}", one), "17");
+    }
+
+    #[test]
+    fn test_matches_fp() {
+        for i in 0..P {
+            for j in 0..P {
+                let (a, b): (Fp<P>, Fp<P>) = (Fp::new(i), Fp::new(j));
+                let (c, d): (F19, F19) = (FpMont::new(i), FpMont::new(j));
+
+                assert_eq!((a + b).get(), (c + d).get());
+                assert_eq!((a - b).get(), (c - d).get());
+                assert_eq!((a * b).get(), (c * d).get());
+                assert_eq!((-a).get(), (-c).get());
+                assert_eq!(a.pow(j).get(), c.pow(j).get());
+                if j != 0 {
+                    assert_eq!((a / b).get(), (c / d).get());
+                    assert_eq!((a % b).get(), (c % d).get());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        #[derive(Debug)]
+        struct P257;
+        impl MontgomeryParams<1> for P257 {
+            const MODULUS: [u64; 1] = [257];
+        }
+
+        for i in 0..P {
+            let (a, b): (Fp<P>, F19) = (Fp::new(i), FpMont::new(i));
+            assert_eq!(a.legendre(), b.legendre());
+            assert_eq!(
+                a.sqrt().map(|r| r.pow(2).get()),
+                b.sqrt().map(|r| r.pow(2).get())
+            );
+        }
+        for i in 0..257 {
+            let (a, b): (Fp<257>, FpMont<P257, 1>) = (Fp::new(i), FpMont::new(i));
+            assert_eq!(a.legendre(), b.legendre());
+            assert_eq!(
+                a.sqrt().map(|r| r.pow(2).get()),
+                b.sqrt().map(|r| r.pow(2).get())
+            );
+        }
+    }
+
+    #[test]
+    fn test_matches_fp_64_bit() {
+        const Q: T = 0xffffffff00000001;
+        let mut x: T = 0x0123456789abcdef;
+        for _ in 0..100 {
+            x = (x * 6364136223846793005 + 1442695040888963407) % (1 << 64);
+            let (a, b): (Fp<Q>, Fp<Q>) = (Fp::new(x), Fp::new(x >> 7));
+            let (c, d): (FpMont<Goldilocks, 1>, FpMont<Goldilocks, 1>) =
+                (FpMont::new(x), FpMont::new(x >> 7));
+
+            assert_eq!((a + b).get(), (c + d).get());
+            assert_eq!((a - b).get(), (c - d).get());
+            assert_eq!((a * b).get(), (c * d).get());
+            assert_eq!(a.inv().get(), c.inv().get());
+        }
+    }
+
+    #[test]
+    fn test_two_limbs() {
+        const Q: T = (1 << 127) - 1;
+        let a: FpMont<Mersenne127, 2> = FpMont::new(Q - 1);
+
+        assert_eq!(FpMont::<Mersenne127, 2>::new(Q), a.zero());
+        assert_eq!(a.get(), Q - 1);
+        assert_eq!(a * a, a.one());
+        assert_eq!(a + a.one(), a.zero());
+        // 2^127 = 1 mod Q
+        assert_eq!(FpMont::<Mersenne127, 2>::new(2).pow(127), a.one());
+    }
+
+    #[test]
+    fn test_secp256k1() {
+        let minus_one: FpMont<Secp256k1, 4> = -FpMont::new(1);
+        let a: FpMont<Secp256k1, 4> = FpMont::new(0xdeadbeef_u128 << 80);
+
+        assert_eq!(
+            minus_one.to_limbs(),
+            [0xfffffffefffffc2e, u64::MAX, u64::MAX, u64::MAX]
+        );
+        assert_eq!(minus_one * minus_one, a.one());
+        assert_eq!(a * a.inv(), a.one());
+        assert_eq!(a / a, a.one());
+        assert_eq!((a * a).sqrt().map(|s| s * s), Some(a * a));
+        assert_eq!(minus_one.sqrt(), None);
+    }
+
+    #[test]
+    fn test_p256() {
+        let a: FpMont<P256, 4> = FpMont::from_be_bytes(&[0xff; 32]);
+        let b: FpMont<P256, 4> = FpMont::new(u128::MAX);
+
+        // 2^256 - 1 - p = 2^224 - 2^192 - 2^96
+        assert_eq!(
+            a.to_limbs(),
+            [
+                0,
+                0xffffffff00000000,
+                0xffffffffffffffff,
+                0x00000000fffffffe
+            ]
+        );
+        assert_eq!((a + b) - b, a);
+        assert_eq!((a * b) / b, a);
+        assert_eq!(a.pow(3), a * a * a);
+    }
+
+    #[test]
+    fn test_polynomial_and_ec() {
+        let p1: Polynomial<F19> = Polynomial::new_from_slice(&[1, 2, 3]);
+        let p2: Polynomial<F19> = Polynomial::new_from_slice(&[4, 5, 6]);
+        assert_eq!(p1 * p2, Polynomial::new_from_slice(&[4, 13, 28, 27, 18]));
+
+        let fp_ec = Ec::new(Fp::<P>::new(2), Fp::new(2));
+        let mont_ec: Ec<F19> = Ec::new(FpMont::new(2), FpMont::new(2));
+        let fp_point = EcPoint::new(Fp::new(1), Fp::new(9), fp_ec).unwrap();
+        let mont_point = EcPoint::new(FpMont::new(1), FpMont::new(9), mont_ec).unwrap();
+
+        for i in 1..20 {
+            match (fp_point.clone() * i, mont_point.clone() * i) {
+                (EcPoint::Infinity, EcPoint::Infinity) => {}
+                (EcPoint::Point { x, y, .. }, EcPoint::Point { x: mx, y: my, .. }) => {
+                    assert_eq!((x.get(), y.get()), (mx.get(), my.get()));
+                }
+                _ => panic!("Results differ for {}", i),
+            }
+        }
+    }
+}
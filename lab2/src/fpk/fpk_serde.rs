@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::{Fpk, T};
 use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
@@ -60,7 +62,7 @@ impl<const P: T, const K: T> Serialize for Fpk<P, K> {
         state.serialize_field("P", &P)?;
         state.serialize_field("K", &K)?;
         state.serialize_field("Poly", &self.poly)?;
-        state.serialize_field("Modulo", &self.modulo)?;
+        state.serialize_field("Modulo", self.modulo.as_ref())?;
         state.end()
     }
 }
@@ -133,7 +135,10 @@ impl<'de, const P: T, const K: T> Deserialize<'de> for Fpk<P, K> {
                 let poly = poly.ok_or_else(|| serde::de::Error::missing_field("poly"))?;
                 let modulo = modulo.ok_or_else(|| serde::de::Error::missing_field("modulo"))?;
 
-                Ok(Fpk { poly, modulo })
+                Ok(Fpk {
+                    poly,
+                    modulo: Arc::new(modulo),
+                })
             }
         }
 
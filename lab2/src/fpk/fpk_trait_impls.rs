@@ -1,11 +1,18 @@
 use std::fmt::Display;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use num_bigint::BigUint;
+use rand::Rng;
 
 use super::{Fpk, T};
 use crate::fp::Fp;
-use crate::polynomials::Polynomial;
-use crate::traits::needed_impls::gcd;
-use crate::traits::{Inverse, Pow, Sqrt};
+use crate::polynomials::{ModulusErrors, ParseErrors, Polynomial, split_modulus};
+use crate::scalar::ConditionalSwap;
+use crate::traits::needed_impls::{gcd, tonelli_shanks};
+use crate::traits::{FiniteField, Inverse, Parity, Pow, Sqrt};
 
 impl<const P: T, const K: T> Fpk<P, K> {
     pub fn new(poly: Polynomial<Fp<P>>, modulo: Polynomial<Fp<P>>) -> Self {
@@ -13,22 +20,65 @@ impl<const P: T, const K: T> Fpk<P, K> {
             modulo.degree().expect("modulo must have a positive degree"),
             K as usize
         );
+        Self::from_poly(poly, &Arc::new(modulo))
+    }
+
+    /// poly reduced modulo `modulo`, sharing it.
+    fn from_poly(poly: Polynomial<Fp<P>>, modulo: &Arc<Polynomial<Fp<P>>>) -> Self {
         Self {
-            poly: poly % modulo.clone(),
-            modulo,
+            poly: poly.div_rem(modulo).1,
+            modulo: modulo.clone(),
+        }
+    }
+
+    /// Like `new`, but refuses moduli that are not irreducible of degree K,
+    /// which do not give a field.
+    pub fn new_checked(
+        poly: Polynomial<Fp<P>>,
+        modulo: Polynomial<Fp<P>>,
+    ) -> Result<Self, ModulusErrors> {
+        if modulo.degree() != Some(K as usize) {
+            return Err(ModulusErrors::WrongDegree);
+        }
+        if !modulo.is_irreducible() {
+            return Err(ModulusErrors::Reducible);
         }
+        Ok(Self::new(poly, modulo))
+    }
+
+    /// Parses the notation of `Display`, which leaves out the modulus, over `modulo`
+    /// as in `new`.
+    pub fn parse_with_modulus(s: &str, modulo: &Polynomial<Fp<P>>) -> Result<Self, ParseErrors> {
+        let poly: Polynomial<Fp<P>> = s.parse()?;
+        if poly.degree().is_some_and(|degree| degree >= K as usize) {
+            return Err(ParseErrors::DegreeTooLarge);
+        }
+        Ok(Self::new(poly, modulo.clone()))
+    }
+
+    /// Element over the sparse modulus from `Polynomial::sparse_irreducible`.
+    /// The search runs on every call, so further elements should reuse the modulus.
+    pub fn with_sparse_modulus(poly: Polynomial<Fp<P>>) -> Self {
+        let modulo = Polynomial::<Fp<P>>::sparse_irreducible(K as usize)
+            .expect("No sparse irreducible polynomial of this degree");
+        Self::new(poly, modulo)
+    }
+
+    /// Element over a random monic irreducible modulus of degree K.
+    pub fn with_random_modulus<R: Rng + ?Sized>(poly: Polynomial<Fp<P>>, rng: &mut R) -> Self {
+        Self::new(
+            poly,
+            Polynomial::<Fp<P>>::random_irreducible(K as usize, rng),
+        )
     }
 
     fn is_zero(&self) -> bool {
         self.poly.is_zero()
     }
 
+    /// Compares the moduli, by pointer first.
     fn match_mods(lhs: &Self, rhs: &Self) -> bool {
-        lhs.modulo
-            .coefficients()
-            .iter()
-            .zip(rhs.modulo.coefficients().iter())
-            .all(|(a, b)| a == b)
+        Arc::ptr_eq(&lhs.modulo, &rhs.modulo) || lhs.modulo == rhs.modulo
     }
 
     fn _degree(&self) -> Option<usize> {
@@ -38,20 +88,116 @@ impl<const P: T, const K: T> Fpk<P, K> {
     pub fn coefficients(&self) -> Vec<Fp<P>> {
         self.poly.coefficients()
     }
+
+    /// Number of elements of the field, P^K.
+    pub fn order() -> T {
+        P.checked_pow(K as u32)
+            .expect("Order of the field does not fit in T")
+    }
+
+    /// Quadratic character of the element, the analogue of the Legendre symbol for F_{P^K}:
+    /// 0 for zero, 1 for non-zero squares and -1 otherwise.
+    pub fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            0
+        } else if P == 2 || self.clone().pow((Self::order() - 1) / 2) == self.one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Element whose coefficients are the base P digits of `index`.
+    fn element_at(&self, mut index: T) -> Self {
+        let mut coef = vec![];
+        while index > 0 {
+            coef.push(Fp::new(index % P));
+            index /= P;
+        }
+        Self::from_poly(Polynomial::new(coef), &self.modulo)
+    }
+}
+
+/// Swaps all K coefficients, the missing leading ones taken as zero. The moduli stay,
+/// elements of one field have equal ones.
+impl<const P: T, const K: T> ConditionalSwap for Fpk<P, K> {
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+        let padded = |poly: &Polynomial<Fp<P>>| {
+            let mut coef = poly.coefficients();
+            coef.resize(K as usize, Fp::new(0));
+            coef
+        };
+        let (mut a, mut b) = (padded(&self.poly), padded(&other.poly));
+        for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+            x.conditional_swap(y, mask);
+        }
+        (self.poly, other.poly) = (Polynomial::new(a), Polynomial::new(b));
+    }
 }
 
 impl<const P: T, const K: T> Pow for Fpk<P, K> {
     fn zero(&self) -> Self {
-        Self::new(self.modulo.zero(), self.modulo.clone())
+        Self::from_poly(self.modulo.zero(), &self.modulo)
     }
     fn one(&self) -> Self {
-        Self::new(self.modulo.one(), self.modulo.clone())
+        Self::from_poly(self.modulo.one(), &self.modulo)
     }
 }
 
+impl<const P: T, const K: T> FiniteField for Fpk<P, K> {
+    fn order(&self) -> BigUint {
+        BigUint::from(P).pow(K as u32)
+    }
+
+    /// Digits of the index in base P are the coefficients.
+    fn element_at(&self, mut index: T) -> Self {
+        let mut coefficients = Vec::with_capacity(K as usize);
+        while index > 0 {
+            coefficients.push(Fp::new(index % P));
+            index /= P;
+        }
+        Self::from_poly(Polynomial::new(coefficients), &self.modulo)
+    }
+}
+
+impl<const P: T, const K: T> Parity for Fpk<P, K> {
+    /// Parity of the lowest non-zero coefficient, which differs between y and -y for odd P.
+    fn is_odd(&self) -> bool {
+        self.coefficients()
+            .iter()
+            .find(|c| c.get() != 0)
+            .is_some_and(|c| c.is_odd())
+    }
+}
+
+/// Hashes the reduced polynomial only, elements of different fields that share it
+/// are unequal anyway.
+impl<const P: T, const K: T> Hash for Fpk<P, K> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.poly.hash(state);
+    }
+}
+
+/// The polynomial, and with `{:#}` also ` mod ` and the modulus.
 impl<const P: T, const K: T> Display for Fpk<P, K> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.poly)
+        match f.alternate() {
+            true => write!(f, "{} mod {}", self.poly, self.modulo),
+            false => write!(f, "{}", self.poly),
+        }
+    }
+}
+
+/// Reads the notation of `{:#}`, `<element> mod <modulus>`.
+impl<const P: T, const K: T> FromStr for Fpk<P, K> {
+    type Err = ParseErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (element, modulo): (_, Polynomial<Fp<P>>) = split_modulus(s)?;
+        if modulo.degree() != Some(K as usize) {
+            return Err(ParseErrors::WrongModulusDegree);
+        }
+        Self::parse_with_modulus(element, &modulo)
     }
 }
 
@@ -60,7 +206,7 @@ impl<const P: T, const K: T> Neg for Fpk<P, K> {
 
     fn neg(self) -> Self {
         Self {
-            poly: (self.modulo.clone() - self.poly) % self.modulo.clone(),
+            poly: -self.poly,
             modulo: self.modulo,
         }
     }
@@ -96,7 +242,7 @@ impl<const P: T, const K: T> Mul for Fpk<P, K> {
         assert!(Self::match_mods(&self, &other));
 
         Self {
-            poly: (self.poly * other.poly) % other.modulo,
+            poly: (self.poly * other.poly).div_rem(&self.modulo).1,
             modulo: self.modulo,
         }
     }
@@ -131,15 +277,42 @@ impl<const P: T, const K: T> Rem for Fpk<P, K> {
 }
 
 impl<const P: T, const K: T> Sqrt for Fpk<P, K> {
+    /// Tonelli–Shanks over F_q with q = P^K, with shortcuts for q = 3 mod 4 and
+    /// for characteristic 2, where the square root is the inverse Frobenius a^(q/2).
     fn sqrt(self) -> Option<Self> {
-        Some(self)
+        let q = Self::order();
+        if P == 2 {
+            return Some(self.pow(q / 2));
+        }
+        match self.legendre() {
+            0 => return Some(self),
+            -1 => return None,
+            _ if q % 4 == 3 => return Some(self.pow((q + 1) / 4)),
+            _ => {}
+        }
+
+        let s = (q - 1).trailing_zeros();
+        let t = (q - 1) >> s;
+        let z = (2..q)
+            .map(|i| self.element_at(i))
+            .find(|z| z.legendre() == -1)
+            .expect("Every field of odd characteristic has a non-residue");
+
+        tonelli_shanks(
+            self.clone().pow(t.div_ceil(2)),
+            self.clone().pow(t),
+            z.pow(t),
+            s,
+        )
     }
 }
 
 impl<const P: T, const K: T> Inverse for Fpk<P, K> {
     fn inv(self) -> Self {
-        let (g, x, _) = gcd(self.poly, self.modulo.clone());
-        assert_eq!(g, g.one(), "Element is not invertible");
-        Self::new(x, self.modulo)
+        let (g, x, _) = gcd(self.poly, self.modulo.as_ref().clone());
+        // For an invertible element the gcd is a non-zero constant, not necessarily one
+        assert_eq!(g.degree(), Some(0), "Element is not invertible");
+        let scale = Polynomial::new(vec![g.coef[0].inv()]);
+        Self::from_poly(x * scale, &self.modulo)
     }
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use super::fp::Fp;
 use super::traits::{Field, Normal};
 use crate::T;
@@ -8,10 +10,14 @@ pub use fpk_serde::deser;
 mod fpk_serde;
 mod fpk_trait_impls;
 
+/// Element of F_{P^K} = F_P[x] / (modulo). The modulus is shared by every element
+/// computed from this one, so checking that two operands have the same modulus is
+/// usually a pointer comparison, and only independently created elements are compared
+/// term by term. `F2m` and the parameters of `Ec` are shared the same way.
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Fpk<const P: T, const K: T> {
     poly: Polynomial<Fp<P>>,
-    modulo: Polynomial<Fp<P>>,
+    modulo: Arc<Polynomial<Fp<P>>>,
 }
 
 impl<const P: T, const K: T> Field for Fpk<P, K> {}
@@ -20,8 +26,34 @@ impl<const P: T, const K: T> Normal for Fpk<P, K> {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        polynomials::{ModulusErrors, ParseErrors},
+        traits::{Inverse, Pow, Sqrt, check_shared_modulus},
+    };
     const P: T = 19;
 
+    #[test]
+    fn test_checked_creation() {
+        let poly: Polynomial<Fp<P>> = Polynomial::new_from_slice(&[3, 4]);
+        // 19 = 3 mod 4, so x^2 + 1 is irreducible, while x^2 - 1 is not
+        let modulo = Polynomial::new_from_slice(&[1, 0, 1]);
+        assert!(Fpk::<P, 2>::new_checked(poly.clone(), modulo.clone()).is_ok());
+        assert_eq!(
+            Fpk::<P, 3>::new_checked(poly.clone(), modulo),
+            Err(ModulusErrors::WrongDegree)
+        );
+        let reducible = Polynomial::new_from_slice(&[P - 1, 0, 1]);
+        assert_eq!(
+            Fpk::<P, 2>::new_checked(poly.clone(), reducible),
+            Err(ModulusErrors::Reducible)
+        );
+
+        let sparse = Fpk::<P, 5>::with_sparse_modulus(poly.clone());
+        assert!(sparse.modulo.is_irreducible());
+        let random = Fpk::<P, 6>::with_random_modulus(poly, &mut rand::rng());
+        assert!(random.modulo.is_irreducible());
+    }
+
     #[test]
     fn test_creation() {
         const K: T = 2;
@@ -63,6 +95,37 @@ mod tests {
         assert_eq!(format!("{}", p1), "2*x^5 + x^3 + 10*x^2 + 4*x + 3");
     }
 
+    #[test]
+    fn test_parse() {
+        let s = "2*x^5 + x^3 + 10*x^2 + 4*x + 3";
+        let modulo = Polynomial::<Fp<P>>::sparse_irreducible(8).unwrap();
+        let fpk: Fpk<P, 8> = Fpk::parse_with_modulus(s, &modulo).unwrap();
+        assert_eq!(fpk.to_string(), s);
+        let poly = Polynomial::new_from_slice(&[3, 4, 10, 1, 0, 2]);
+        assert_eq!(fpk, Fpk::new(poly, modulo.clone()));
+        assert_eq!(
+            Fpk::<P, 8>::parse_with_modulus("x^8", &modulo),
+            Err(ParseErrors::DegreeTooLarge)
+        );
+        assert_eq!(
+            Fpk::<P, 8>::parse_with_modulus("19*x", &modulo),
+            Err(ParseErrors::NonCanonicalCoefficient(0))
+        );
+
+        let full = format!("{:#}", fpk);
+        assert_eq!(full, format!("{} mod {}", s, modulo));
+        assert_eq!(full.parse(), Ok(fpk));
+        assert_eq!(s.parse::<Fpk<P, 8>>(), Err(ParseErrors::MissingModulus));
+        assert_eq!(
+            "x mod x^2 + 1".parse::<Fpk<P, 8>>(),
+            Err(ParseErrors::WrongModulusDegree)
+        );
+        assert_eq!(
+            "x mod x^8 + 19".parse::<Fpk<P, 8>>(),
+            Err(ParseErrors::NonCanonicalCoefficient(12))
+        );
+    }
+
     #[test]
     fn test_add() {
         const K: T = 8;
@@ -149,6 +212,15 @@ mod tests {
         assert_eq!(p1.clone() * p2, expected);
     }
 
+    #[test]
+    fn test_shared_modulus() {
+        const K: T = 2;
+        let pk: Polynomial<Fp<P>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let p1: Fpk<P, K> = Fpk::new(Polynomial::new_from_slice(&[3, 4]), pk.clone());
+        let p2: Fpk<P, K> = Fpk::new(Polynomial::new_from_slice(&[5, 1]), pk);
+        check_shared_modulus(p1, p2, |p| &p.modulo);
+    }
+
     #[test]
     fn test_division() {
         const K: T = 6;
@@ -180,6 +252,18 @@ mod tests {
         assert_eq!(p1 / p2, expected);
     }
 
+    #[test]
+    fn test_inverse() {
+        // The gcd with the modulus is often a constant other than one
+        let pk: Polynomial<Fp<3>> = Polynomial::new_from_slice(&[1, 2, 0, 1]);
+        for i in 1..27 {
+            let poly = Polynomial::new_from_slice(&[i % 3, i / 3 % 3, i / 9]);
+            let x: Fpk<3, 3> = Fpk::new(poly, pk.clone());
+
+            assert_eq!(x.clone() * x.clone().inv(), x.one());
+        }
+    }
+
     #[test]
     fn test_remainder() {
         const K: T = 6;
@@ -210,4 +294,36 @@ mod tests {
 
         assert_eq!(p1 % p2, expected);
     }
+
+    fn check_sqrt<const Q: T, const D: T>(modulo: &[T]) {
+        let modulo: Polynomial<Fp<Q>> = Polynomial::new_from_slice(modulo);
+        let elements: Vec<Fpk<Q, D>> = (0..Q.pow(D as u32))
+            .map(|i| {
+                let coef: Vec<T> = (0..D).map(|j| i / Q.pow(j as u32) % Q).collect();
+                Fpk::new(Polynomial::new_from_slice(&coef), modulo.clone())
+            })
+            .collect();
+        let squares: Vec<Fpk<Q, D>> = elements.iter().map(|e| e.clone() * e.clone()).collect();
+
+        for a in elements {
+            match a.clone().sqrt() {
+                Some(root) => assert_eq!(root.clone() * root, a),
+                None => assert!(!squares.contains(&a), "missed root of {}", a),
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt() {
+        // q = 3 mod 4
+        check_sqrt::<3, 3>(&[1, 2, 0, 1]);
+        check_sqrt::<7, 1>(&[0, 1]);
+        // q = 1 mod 4
+        check_sqrt::<3, 2>(&[1, 0, 1]);
+        check_sqrt::<5, 2>(&[2, 0, 1]);
+        check_sqrt::<7, 2>(&[1, 0, 1]);
+        check_sqrt::<13, 1>(&[0, 1]);
+        // characteristic 2
+        check_sqrt::<2, 3>(&[1, 1, 0, 1]);
+    }
 }
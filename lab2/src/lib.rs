@@ -1,10 +1,14 @@
 use num_bigint::BigUint;
 
+pub mod arithmetic;
+pub mod curves;
 pub mod elliptic_curve;
 pub mod f2m;
 pub mod fp;
+pub mod fp_mont;
 pub mod fpk;
 pub mod polynomials;
+pub mod scalar;
 pub mod traits;
 
 pub type T = u128;
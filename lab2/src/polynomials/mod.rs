@@ -1,19 +1,128 @@
 use super::traits::Field;
 
 mod poly_f2m_trait_impls;
+mod poly_factorization;
+mod poly_field_impls;
+mod poly_irreducible;
+mod poly_multiplication;
+mod poly_parse;
 mod poly_serde;
 mod poly_trait_impls;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) use poly_parse::split_modulus;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Polynomial<T> {
     pub(crate) coef: Vec<T>,
 }
 
 impl<T: Field> Field for Polynomial<T> {}
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ModulusErrors {
+    /// The modulus does not have the degree of the field.
+    WrongDegree,
+    /// The modulus factors, so the quotient ring is not a field.
+    Reducible,
+}
+
+impl std::fmt::Display for ModulusErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            ModulusErrors::WrongDegree => "modulus does not have the degree of the field",
+            ModulusErrors::Reducible => "modulus is not irreducible",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ModulusErrors {}
+
+/// Errors of `FromStr` for polynomials and field elements, and of `parse_with_modulus`.
+/// Positions are byte offsets.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ParseErrors {
+    /// The input has no terms.
+    Empty,
+    /// A character that does not fit the notation.
+    UnexpectedCharacter(usize),
+    /// The input ends in the middle of a term.
+    IncompleteTerm(usize),
+    /// A term whose coefficient is zero, not below the characteristic, has a leading zero
+    /// or is a 1 written before x.
+    NonCanonicalCoefficient(usize),
+    /// An exponent below 2, which is written as `x` or left out, or one that is too large.
+    NonCanonicalExponent(usize),
+    /// A term whose degree is not below the degree of the one before it.
+    TermsOutOfOrder(usize),
+    /// An element of degree at least the degree of the field.
+    DegreeTooLarge,
+    /// A field element without ` mod ` and its modulus after it.
+    MissingModulus,
+    /// A modulus whose degree is not the degree of the field.
+    WrongModulusDegree,
+}
+
+impl ParseErrors {
+    /// The same error with its position moved by `offset`, for a part of a longer input.
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        match self {
+            ParseErrors::UnexpectedCharacter(i) => ParseErrors::UnexpectedCharacter(i + offset),
+            ParseErrors::IncompleteTerm(i) => ParseErrors::IncompleteTerm(i + offset),
+            ParseErrors::NonCanonicalCoefficient(i) => {
+                ParseErrors::NonCanonicalCoefficient(i + offset)
+            }
+            ParseErrors::NonCanonicalExponent(i) => ParseErrors::NonCanonicalExponent(i + offset),
+            ParseErrors::TermsOutOfOrder(i) => ParseErrors::TermsOutOfOrder(i + offset),
+            other => other,
+        }
+    }
+}
+
+impl std::fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrors::Empty => write!(f, "nothing to parse"),
+            ParseErrors::UnexpectedCharacter(i) => write!(f, "unexpected character at byte {}", i),
+            ParseErrors::IncompleteTerm(i) => write!(f, "incomplete term at byte {}", i),
+            ParseErrors::NonCanonicalCoefficient(i) => {
+                write!(
+                    f,
+                    "coefficient of the term at byte {} is not in canonical form",
+                    i
+                )
+            }
+            ParseErrors::NonCanonicalExponent(i) => {
+                write!(f, "exponent at byte {} is not in canonical form", i)
+            }
+            ParseErrors::TermsOutOfOrder(i) => {
+                write!(
+                    f,
+                    "term at byte {} is not of lower degree than the one before",
+                    i
+                )
+            }
+            ParseErrors::DegreeTooLarge => write!(f, "degree is not below the degree of the field"),
+            ParseErrors::MissingModulus => write!(f, "no modulus given after ` mod `"),
+            ParseErrors::WrongModulusDegree => {
+                write!(f, "degree of the modulus is not the degree of the field")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseErrors {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        T,
+        f2m::{F2m, bit::Bits8},
+        fp::Fp,
+        traits::{FieldBytes, FiniteField, Pow},
+    };
+    use rand::Rng;
 
     #[test]
     fn test_creation() {
@@ -38,6 +147,21 @@ mod tests {
         assert_eq!(p1 - p2, expected);
     }
 
+    #[test]
+    fn test_division_by_constant() {
+        // A constant remainder is still divisible by a constant divisor
+        type F = crate::fp::Fp<7>;
+        let p: Polynomial<F> = Polynomial::new_from_slice(&[3, 2, 5]);
+        let c: Polynomial<F> = Polynomial::new_from_slice(&[2]);
+
+        assert_eq!(p.clone() % c.clone(), Polynomial::new(vec![]));
+        assert_eq!((p.clone() / c.clone()) * c.clone(), p);
+        assert_eq!(
+            Polynomial::new_from_slice(&[4]) / c,
+            Polynomial::<F>::new_from_slice(&[2])
+        );
+    }
+
     #[test]
     fn test_multiplication() {
         let p1: Polynomial<isize> = <Polynomial<isize>>::new(vec![1, 2, 3]);
@@ -45,4 +169,307 @@ mod tests {
         let expected = <Polynomial<isize>>::new(vec![4, 13, 28, 27, 18]);
         assert_eq!(p1 * p2, expected);
     }
+
+    /// Random products of lengths around the thresholds, against schoolbook.
+    /// Roots of unity in F_P have orders up to 2^two_adicity, which limits the NTT.
+    fn check_multiplication<const P: T>(two_adicity: u32) {
+        let mut rng = rand::rng();
+        let mut random =
+            |length: usize| -> Vec<Fp<P>> { (0..length).map(|_| Fp::new(rng.random())).collect() };
+        for (m, n) in [
+            (1, 1),
+            (5, 40),
+            (31, 33),
+            (64, 64),
+            (70, 200),
+            (150, 129),
+            (300, 257),
+        ] {
+            let (a, b) = (random(m), random(n));
+            let expected = poly_multiplication::schoolbook(&a, &b);
+            assert_eq!(poly_multiplication::karatsuba(&a, &b), expected);
+            let log_n = (m + n - 1).next_power_of_two().trailing_zeros();
+            let product = poly_multiplication::ntt_multiply(&a, &b);
+            assert_eq!(product.is_some(), log_n <= two_adicity);
+            if let Some(product) = product {
+                assert_eq!(product, expected);
+            }
+            assert_eq!(
+                Polynomial::new(a) * Polynomial::new(b),
+                Polynomial::new(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_multiplication_methods() {
+        // 2^64 - 2^32 + 1 and 119 * 2^23 + 1 have roots of unity of order 2^32 and 2^23,
+        // 2^61 - 1 only of order 2
+        check_multiplication::<18446744069414584321>(32);
+        check_multiplication::<998244353>(23);
+        check_multiplication::<2305843009213693951>(1);
+
+        let root = Fp::<998244353>::new(1).root_of_unity(23).unwrap();
+        assert_eq!(root.pow(1 << 22), Fp::new(998244352));
+        assert_eq!(Fp::<998244353>::new(1).root_of_unity(24), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        for s in [
+            "2*x^5 + x^3 + 10*x^2 + 4*x + 3",
+            "x + 1",
+            "1",
+            "0",
+            "x^12",
+            "7*x",
+        ] {
+            assert_eq!(s.parse::<Polynomial<Fp<11>>>().unwrap().to_string(), s);
+        }
+        assert_eq!(
+            Polynomial::<Fp<11>>::new_from_slice(&[1, 1]).to_string(),
+            "x + 1"
+        );
+        assert_eq!(
+            "2 * x^5+x".parse::<Polynomial<Fp<11>>>(),
+            Ok(Polynomial::new_from_slice(&[0, 1, 0, 0, 0, 2]))
+        );
+
+        let error = |s: &str| s.parse::<Polynomial<Fp<11>>>().unwrap_err();
+        assert_eq!(error(" "), ParseErrors::Empty);
+        assert_eq!(error("x +"), ParseErrors::IncompleteTerm(3));
+        assert_eq!(error("x^"), ParseErrors::IncompleteTerm(2));
+        assert_eq!(error("x + y"), ParseErrors::UnexpectedCharacter(4));
+        assert_eq!(error("x^2 + 1*x"), ParseErrors::NonCanonicalCoefficient(6));
+        assert_eq!(error("11*x"), ParseErrors::NonCanonicalCoefficient(0));
+        assert_eq!(error("x + 03"), ParseErrors::NonCanonicalCoefficient(4));
+        assert_eq!(error("x^1"), ParseErrors::NonCanonicalExponent(2));
+        assert_eq!(error("x + x^2"), ParseErrors::TermsOutOfOrder(4));
+
+        let s = "x^113 + x^9 + 1";
+        let f: Polynomial<Bits8> = s.parse().unwrap();
+        assert_eq!(f, Polynomial::<Bits8>::sparse_irreducible(113).unwrap());
+        assert_eq!(f.to_string(), s);
+        assert_eq!(
+            "2*x".parse::<Polynomial<Bits8>>(),
+            Err(ParseErrors::NonCanonicalCoefficient(0))
+        );
+    }
+
+    #[test]
+    fn test_gcd_and_evaluation() {
+        let poly = |coef: &[T]| Polynomial::<Fp<101>>::new_from_slice(coef);
+        let a = poly(&[1, 1]) * poly(&[2, 1]) * poly(&[3, 0, 1]);
+        let b = poly(&[1, 1]) * poly(&[5, 1]);
+        let (g, s, t) = a.extended_gcd(&b);
+        assert_eq!(g, poly(&[1, 1]));
+        assert_eq!(s * a.clone() + t * b.clone(), g);
+        assert_eq!(a.gcd(&b), g);
+        let (q, r) = a.div_rem(&b);
+        assert_eq!(q * b + r.clone(), a);
+        assert!(r.degree() < Some(2));
+
+        let f = poly(&[1, 2, 0, 3]);
+        assert_eq!(f.evaluate(&Fp::new(5)), Fp::new(83));
+        let points = [0, 1, 5].map(Fp::new);
+        assert_eq!(f.evaluate_many(&points), [1, 6, 83].map(Fp::new));
+        let x = poly(&[0, 1]);
+        let modulus = poly(&[1, 0, 1]);
+        assert_eq!(
+            x.pow_mod(&101_u32.into(), &modulus),
+            x.clone().pow(101).div_rem(&modulus).1
+        );
+    }
+
+    #[test]
+    fn test_derivative_and_composition() {
+        let f = <Polynomial<isize>>::new(vec![8, 0, -6, 1]);
+        assert_eq!(f.derivative(), <Polynomial<isize>>::new(vec![0, -12, 3]));
+        assert_eq!(f.evaluate(&2), -8);
+        let inner = <Polynomial<isize>>::new(vec![1, 1]);
+        assert_eq!(
+            f.compose(&inner),
+            <Polynomial<isize>>::new(vec![3, -9, -3, 1])
+        );
+
+        // (x^2)' = 2x vanishes over GF(2^m)
+        let aes = [Bits8(0x1b), Bits8(1)];
+        let element = |byte| F2m::<8>::new_from_slice(&[Bits8(byte)], &aes);
+        let f = Polynomial::new(vec![element(3), element(7), element(0x53)]);
+        assert_eq!(f.derivative(), Polynomial::new(vec![element(7)]));
+
+        let poly = |coef: &[T]| Polynomial::<Fp<101>>::new_from_slice(coef);
+        let (f, inner, modulus) = (
+            poly(&[4, 0, 3, 1]),
+            poly(&[7, 5, 1]),
+            poly(&[2, 9, 0, 0, 1]),
+        );
+        assert_eq!(
+            f.compose(&inner).evaluate(&Fp::new(10)),
+            f.evaluate(&inner.evaluate(&Fp::new(10)))
+        );
+        assert_eq!(
+            f.compose_mod(&inner, &modulus),
+            f.compose(&inner).div_rem(&modulus).1
+        );
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let f = Polynomial::<Fp<101>>::new_from_slice(&[17, 0, 42, 5, 99]);
+        let points: Vec<_> = [3, 8, 20, 55, 100]
+            .map(Fp::new)
+            .into_iter()
+            .map(|x| (x, f.evaluate(&x)))
+            .collect();
+        assert_eq!(Polynomial::lagrange_interpolation(&points), f);
+        assert_eq!(Polynomial::newton_interpolation(&points), f);
+        assert!(Polynomial::<Fp<101>>::lagrange_interpolation(&[]).is_zero());
+
+        let aes = [Bits8(0x1b), Bits8(1)];
+        let element = |byte| F2m::<8>::new_from_slice(&[Bits8(byte)], &aes);
+        let f = Polynomial::new(vec![element(0xca), element(1), element(0x53)]);
+        let points: Vec<_> = [2, 9, 0xfe]
+            .map(element)
+            .into_iter()
+            .map(|x| (x.clone(), f.evaluate(&x)))
+            .collect();
+        assert_eq!(Polynomial::lagrange_interpolation(&points), f);
+        assert_eq!(Polynomial::newton_interpolation(&points), f);
+    }
+
+    #[test]
+    fn test_irreducible_fp() {
+        // (5^3 - 5) / 3 monic irreducible cubics and phi(24) / 2 primitive quadratics over F_5
+        let cubics =
+            (0..125).map(|i| Polynomial::<Fp<5>>::new_from_slice(&[i % 5, i / 5 % 5, i / 25, 1]));
+        assert_eq!(cubics.filter(|f| f.is_irreducible()).count(), 40);
+        let quadratics = (0..25).map(|i| Polynomial::<Fp<5>>::new_from_slice(&[i % 5, i / 5, 1]));
+        assert_eq!(quadratics.filter(|f| f.is_primitive()).count(), 4);
+
+        assert!(Polynomial::<Fp<47>>::new_from_slice(&[1, 0, 1]).is_irreducible());
+        assert!(!Polynomial::<Fp<17>>::new_from_slice(&[1, 0, 1]).is_irreducible());
+
+        let mut rng = rand::rng();
+        let f = Polynomial::<Fp<1000003>>::random_irreducible(7, &mut rng);
+        let g = Polynomial::<Fp<1000003>>::random_irreducible(5, &mut rng);
+        assert_eq!(f.degree(), Some(7));
+        assert!(f.is_irreducible() && g.is_irreducible());
+        assert!(!(f * g).is_irreducible());
+        assert!(Polynomial::<Fp<5>>::random_primitive(4, &mut rng).is_primitive());
+
+        let sparse = Polynomial::<Fp<7>>::sparse_irreducible(12).unwrap();
+        assert!(sparse.is_irreducible());
+        assert!(sparse.coef.iter().filter(|c| c.get() != 0).count() <= 5);
+    }
+
+    #[test]
+    fn test_irreducible_f2m() {
+        // 30 irreducible and 16 primitive polynomials of degree 8 over GF(2)
+        let octics = (0..=255).map(|i| Polynomial::new(vec![Bits8(i), Bits8(1)]));
+        let irreducible: Vec<_> = octics.filter(|f| f.is_irreducible()).collect();
+        assert_eq!(irreducible.len(), 30);
+        assert_eq!(irreducible.iter().filter(|f| f.is_primitive()).count(), 16);
+        // The AES polynomial is irreducible but x has order 51
+        let aes = Polynomial::new(vec![Bits8(0x1b), Bits8(1)]);
+        assert!(aes.is_irreducible() && !aes.is_primitive());
+
+        // Reduction polynomials of sect113r1, sect163k1 and sect233k1
+        let sparse = |n| format!("{}", Polynomial::<Bits8>::sparse_irreducible(n).unwrap());
+        assert_eq!(sparse(113), "x^113 + x^9 + 1");
+        assert_eq!(sparse(163), "x^163 + x^7 + x^6 + x^3 + 1");
+        assert_eq!(sparse(233), "x^233 + x^74 + 1");
+
+        let mut rng = rand::rng();
+        let f = Polynomial::<Bits8>::random_irreducible(200, &mut rng);
+        let g = Polynomial::<Bits8>::random_irreducible(64, &mut rng);
+        assert_eq!(f.degree(), Some(200));
+        assert!(f.is_irreducible() && g.is_irreducible());
+        assert!(!(f * g).is_irreducible());
+        assert!(Polynomial::<Bits8>::random_primitive(31, &mut rng).is_primitive());
+    }
+
+    fn assert_same_factors<F: Field>(
+        factors: Vec<(Polynomial<F>, u32)>,
+        expected: &[(Polynomial<F>, u32)],
+    ) {
+        assert_eq!(factors.len(), expected.len());
+        assert!(expected.iter().all(|factor| factors.contains(factor)));
+    }
+
+    #[test]
+    fn test_factorization_fp() {
+        let poly = |coef: &[T]| Polynomial::<Fp<5>>::new_from_slice(coef);
+        let (a, b, c, d) = (
+            poly(&[1, 1]),
+            poly(&[4, 1]),
+            poly(&[2, 0, 1]),
+            poly(&[1, 1, 0, 1]),
+        );
+        let f = poly(&[3])
+            * a.clone()
+            * a.clone()
+            * b.clone()
+            * c.clone()
+            * d.clone()
+            * d.clone()
+            * d.clone();
+        let expected = [(a, 2), (b, 1), (c, 1), (d, 3)];
+        assert_same_factors(f.factor(), &expected);
+        let degrees: Vec<_> = f.factor().iter().map(|(g, _)| g.degree()).collect();
+        assert_eq!(degrees, [Some(1), Some(1), Some(2), Some(3)]);
+        assert!(poly(&[2]).factor().is_empty());
+        assert!(poly(&[]).factor().is_empty());
+        assert!(poly(&[0]).roots().is_empty());
+
+        // (x^2 + 1)^6 * (x + 2)^3 has a zero derivative over F_3
+        let poly = |coef: &[T]| Polynomial::<Fp<3>>::new_from_slice(coef);
+        let (a, b, c) = (poly(&[0, 1]), poly(&[2, 1]), poly(&[1, 0, 1]));
+        let f = (0..6).fold(a.clone() * b.clone().pow(3), |f, _| f * c.clone());
+        assert_same_factors(f.factor(), &[(a, 1), (b, 3), (c, 6)]);
+
+        // x^2 - 2 is irreducible over F_101
+        let poly = |coef: &[T]| Polynomial::<Fp<101>>::new_from_slice(coef);
+        let f = poly(&[98, 1]) * poly(&[51, 1]) * poly(&[51, 1]) * poly(&[99, 0, 1]);
+        let mut roots: Vec<T> = f.roots().iter().map(|r| r.get()).collect();
+        roots.sort();
+        assert_eq!(roots, [3, 50]);
+        assert!(poly(&[99, 0, 1]).roots().is_empty());
+    }
+
+    #[test]
+    fn test_factorization_f2m() {
+        let aes = [Bits8(0x1b), Bits8(1)];
+        let element = |byte| F2m::<8>::new_from_slice(&[Bits8(byte)], &aes);
+        let linear = |byte| Polynomial::new(vec![element(byte), element(1)]);
+        let quadratic = (1..=255)
+            .map(|c| Polynomial::new(vec![element(c), element(1), element(1)]))
+            .find(|f| f.roots().is_empty())
+            .unwrap();
+        let f = linear(0x53) * linear(0x53) * linear(0xca) * quadratic.clone();
+        let expected = [(linear(0x53), 2), (linear(0xca), 1), (quadratic, 1)];
+        assert_same_factors(f.factor(), &expected);
+        let mut roots: Vec<u8> = f.roots().iter().map(|r| r.to_fixed_bytes()[0]).collect();
+        roots.sort();
+        assert_eq!(roots, [0x53, 0xca]);
+
+        // The GCM field, where nonce reuse gives a polynomial with the key as a root
+        let mut modulo = vec![Bits8(0); 17];
+        (modulo[0], modulo[16]) = (Bits8(0x87), Bits8(1));
+        let sample = F2m::<128>::new_from_slice(&[], &modulo);
+        let mut rng = rand::rng();
+        let mut keys: Vec<_> = (0..3)
+            .map(|_| sample.element_at(rng.random()).to_fixed_bytes())
+            .collect();
+        let f = keys
+            .iter()
+            .fold(Polynomial::new(vec![sample.one()]), |f, key| {
+                let key = sample.parse_fixed_bytes(key).unwrap();
+                f * Polynomial::new(vec![key, sample.one()])
+            });
+        let mut roots: Vec<_> = f.roots().iter().map(|r| r.to_fixed_bytes()).collect();
+        roots.sort();
+        keys.sort();
+        assert_eq!(roots, keys);
+    }
 }
@@ -13,7 +13,8 @@ impl Polynomial<Bits8> {
         }
     }
 
-    pub(crate) fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+    /// Quotient and remainder of long division over GF(2).
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
         let mut remainder = self.clone();
         let divisor = rhs.clone();
 
@@ -51,7 +52,7 @@ impl Polynomial<Bits8> {
         }
     }
 
-    fn set_bit(&mut self, index: usize, value: bool) {
+    pub(crate) fn set_bit(&mut self, index: usize, value: bool) {
         let byte_index = index / 8;
         let bit_index = index % 8;
 
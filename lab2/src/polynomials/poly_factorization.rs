@@ -0,0 +1,182 @@
+use rand::Rng;
+
+use super::Polynomial;
+use crate::{
+    T,
+    arithmetic::gcd,
+    traits::{FiniteField, Inverse},
+};
+
+/// Factorization over a finite field F_q in three stages: square-free factorization,
+/// distinct-degree factorization and Cantor-Zassenhaus equal-degree splitting.
+impl<F: FiniteField + Inverse> Polynomial<F> {
+    /// Monic irreducible factors with their multiplicities, by degree.
+    /// The leading coefficient is left out, so constants have no factors, and neither
+    /// has zero, which is not a product of irreducibles.
+    pub fn factor(&self) -> Vec<(Self, u32)> {
+        if self.is_zero() {
+            return vec![];
+        }
+        let mut rng = rand::rng();
+        let mut factors: Vec<(Self, u32)> = self
+            .square_free_factorization()
+            .into_iter()
+            .flat_map(|(g, multiplicity)| {
+                g.distinct_degree_factorization()
+                    .into_iter()
+                    .flat_map(|(h, degree)| h.equal_degree_factorization(degree, &mut rng))
+                    .map(move |irreducible| (irreducible, multiplicity))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        factors.sort_by_key(|(g, _)| g.degree());
+        factors
+    }
+
+    /// Distinct roots in F_q, read off the linear factors of gcd(x^q - x, self).
+    /// Every element is a root of zero, it is given none rather than all of F_q.
+    pub fn roots(&self) -> Vec<F> {
+        if self.is_zero() {
+            return vec![];
+        }
+        let f = self.monic();
+        let x = Self::new(vec![f.coef[0].zero(), f.coef[0].one()]);
+        let order = f.coef[0].order();
+        let linear = (x.pow_mod(&order, &f) - x).gcd(&f);
+        linear
+            .equal_degree_factorization(1, &mut rand::rng())
+            .into_iter()
+            .map(|g| -g.coef[0].clone())
+            .collect()
+    }
+
+    /// Monic, square-free and pairwise coprime g_i with self = c * prod g_i^i, by Yun's
+    /// algorithm. In characteristic p what is left once the derivative vanishes is a
+    /// p-th power, whose root is factored again.
+    pub fn square_free_factorization(&self) -> Vec<(Self, u32)> {
+        let f = self.monic();
+        let mut factors = Vec::new();
+        let mut c = f.gcd(&f.derivative());
+        let mut w = f.div_rem(&c).0;
+        let mut multiplicity = 1;
+        while w.degree() != Some(0) {
+            let y = w.gcd(&c);
+            let factor = w.div_rem(&y).0;
+            if factor.degree() != Some(0) {
+                factors.push((factor, multiplicity));
+            }
+            c = c.div_rem(&y).0;
+            w = y;
+            multiplicity += 1;
+        }
+        if c.degree() != Some(0) {
+            let (root, p) = c.pth_root();
+            let repeated = root.square_free_factorization().into_iter();
+            factors.extend(repeated.map(|(g, multiplicity)| (g, multiplicity * p)));
+        }
+        factors
+    }
+
+    /// Products of all the irreducible factors of each degree d of a square-free
+    /// polynomial, which divide x^(q^d) - x.
+    pub fn distinct_degree_factorization(&self) -> Vec<(Self, usize)> {
+        let mut f = self.monic();
+        let x = Self::new(vec![f.coef[0].zero(), f.coef[0].one()]);
+        let order = f.coef[0].order();
+        let mut factors = Vec::new();
+        let mut power = x.div_rem(&f).1;
+        let mut d = 1;
+        while f.degree().unwrap_or(0) >= 2 * d {
+            power = power.pow_mod(&order, &f);
+            let g = (power.clone() - x.clone()).gcd(&f);
+            if g.degree() != Some(0) {
+                f = f.div_rem(&g).0;
+                power = power.div_rem(&f).1;
+                factors.push((g, d));
+            }
+            d += 1;
+        }
+        if let Some(n) = f.degree().filter(|&n| n > 0) {
+            factors.push((f, n));
+        }
+        factors
+    }
+
+    /// Cantor-Zassenhaus: splits a square-free product of irreducibles of the given degree
+    /// with gcds against a^((q^d - 1) / 2) - 1 for odd q, or against the trace
+    /// a + a^2 + ... + a^(q^d / 2) for even q, of random a modulo self.
+    pub fn equal_degree_factorization<R: Rng + ?Sized>(
+        &self,
+        degree: usize,
+        rng: &mut R,
+    ) -> Vec<Self> {
+        let f = self.monic();
+        let n = f.degree().unwrap_or(0);
+        if n == 0 {
+            return vec![];
+        }
+        if n == degree {
+            return vec![f];
+        }
+        assert!(
+            n.is_multiple_of(degree),
+            "Factors of equal degree divide the degree"
+        );
+
+        let sample = &f.coef[0];
+        let extension = sample.order().pow(degree as u32);
+        let one = Self::new(vec![sample.one()]);
+        let split = |a: Self| match extension.bit(0) {
+            true => a.pow_mod(&((&extension - 1_u32) >> 1), &f) - one.clone(),
+            false => {
+                let mut term = a.div_rem(&f).1;
+                let mut trace = term.clone();
+                for _ in 1..extension.bits() - 1 {
+                    term = term.mul_mod(&term, &f);
+                    trace = trace + term.clone();
+                }
+                trace
+            }
+        };
+        loop {
+            let a = Self::new((0..n).map(|_| random_element(sample, rng)).collect());
+            let g = split(a).gcd(&f);
+            if let Some(1..) = g.degree().filter(|&d| d < n) {
+                let h = f.div_rem(&g).0;
+                let mut factors = g.equal_degree_factorization(degree, rng);
+                factors.extend(h.equal_degree_factorization(degree, rng));
+                return factors;
+            }
+        }
+    }
+
+    /// g with g^p = self, for p the characteristic, when the derivative is zero. Then
+    /// only powers of x^p appear and p is the prime dividing both their exponents and q.
+    /// A coefficient c has the p-th root c^(q / p).
+    fn pth_root(&self) -> (Self, u32) {
+        let order = self.coef[0].order();
+        let exponents = (1..self.coef.len())
+            .filter(|&i| self.coef[i] != self.coef[i].zero())
+            .fold(0, |acc, i| gcd(acc, i as T));
+        let p = (2..=exponents)
+            .find(|&r| exponents % r == 0 && (&order % r).bits() == 0)
+            .expect("Only p-th powers have a zero derivative");
+        let root = &order / p;
+        let coef = self
+            .coef
+            .iter()
+            .step_by(p as usize)
+            .map(|c| c.clone().pow_big(&root))
+            .collect();
+        (Self::new(coef), p as u32)
+    }
+}
+
+/// Uniformly random element, or one of the first 2^128 of `element_at` in larger fields.
+fn random_element<F: FiniteField, R: Rng + ?Sized>(sample: &F, rng: &mut R) -> F {
+    let index = match T::try_from(sample.order()) {
+        Ok(order) => rng.random_range(0..order),
+        Err(_) => rng.random(),
+    };
+    sample.element_at(index)
+}
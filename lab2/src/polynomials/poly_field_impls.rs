@@ -0,0 +1,199 @@
+use num_bigint::BigUint;
+
+use super::Polynomial;
+use crate::traits::{Field, Inverse};
+
+impl<F: Field> Polynomial<F> {
+    /// Value at x, by Horner's rule.
+    pub fn evaluate(&self, x: &F) -> F {
+        self.coef
+            .iter()
+            .rev()
+            .fold(x.zero(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    /// Values at all the points, with one Horner pass over the coefficients.
+    pub fn evaluate_many(&self, points: &[F]) -> Vec<F> {
+        let mut values: Vec<F> = points.iter().map(|x| x.zero()).collect();
+        for c in self.coef.iter().rev() {
+            for (value, x) in values.iter_mut().zip(points) {
+                *value = value.clone() * x.clone() + c.clone();
+            }
+        }
+        values
+    }
+
+    /// Formal derivative, with i * c computed by doubling and adding c.
+    pub fn derivative(&self) -> Self {
+        let multiple = |c: &F, i: usize| {
+            (0..usize::BITS - i.leading_zeros())
+                .rev()
+                .fold(c.zero(), |acc, bit| match i >> bit & 1 {
+                    1 => acc.clone() + acc + c.clone(),
+                    _ => acc.clone() + acc,
+                })
+        };
+        Polynomial::new(
+            self.coef
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| multiple(c, i))
+                .collect(),
+        )
+    }
+
+    /// self(inner(x)), by Horner's rule.
+    pub fn compose(&self, inner: &Self) -> Self {
+        self.coef
+            .iter()
+            .rev()
+            .fold(Polynomial::new(vec![]), |acc, c| {
+                acc * inner.clone() + Polynomial::new(vec![c.clone()])
+            })
+    }
+}
+
+/// Division and everything built on it: gcds, modular arithmetic and interpolation.
+/// These need `F: Inverse` on top of `Field`, which every field of the crate implements
+/// but integers such as `i128` do not. Coefficients are multiplied by inverses instead
+/// of using `/`, since `Div` is polynomial division for extension field elements.
+impl<F: Field + Inverse> Polynomial<F> {
+    /// Scales the polynomial to leading coefficient one.
+    pub fn monic(&self) -> Self {
+        let lead = self
+            .coef
+            .last()
+            .expect("Zero has no monic multiple")
+            .clone()
+            .inv();
+        Polynomial::new(self.coef.iter().map(|c| c.clone() * lead.clone()).collect())
+    }
+
+    /// Quotient and remainder of long division, for coefficients with an `Inverse`.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let rhs_deg = rhs.degree().expect("Division by zero");
+        if self.coef.len() <= rhs_deg {
+            return (Polynomial::new(vec![]), self.clone());
+        }
+
+        let lead_inv = rhs.coef[rhs_deg].clone().inv();
+        let mut remainder = self.coef.clone();
+        let mut quotient = vec![lead_inv.zero(); remainder.len() - rhs_deg];
+        for i in (rhs_deg..remainder.len()).rev() {
+            let scale = remainder[i].clone() * lead_inv.clone();
+            if scale == scale.zero() {
+                continue;
+            }
+            for (j, coef) in rhs.coef.iter().enumerate() {
+                let k = i - rhs_deg + j;
+                remainder[k] = remainder[k].clone() - scale.clone() * coef.clone();
+            }
+            quotient[i - rhs_deg] = scale;
+        }
+        remainder.truncate(rhs_deg);
+        (Polynomial::new(quotient), Polynomial::new(remainder))
+    }
+
+    /// Monic greatest common divisor, zero only when both are zero.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            (a, b) = (b.clone(), a.div_rem(&b).1);
+        }
+        match a.is_zero() {
+            true => a,
+            false => a.monic(),
+        }
+    }
+
+    /// (g, s, t) with g the monic gcd and s * self + t * other = g.
+    pub fn extended_gcd(&self, other: &Self) -> (Self, Self, Self) {
+        let zero = Polynomial::new(vec![]);
+        let Some(sample) = self.coef.first().or(other.coef.first()) else {
+            return (zero.clone(), zero.clone(), zero);
+        };
+        let one = Polynomial::new(vec![sample.one()]);
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (one.clone(), zero.clone());
+        let (mut t0, mut t1) = (zero, one);
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.div_rem(&r1);
+            (r0, r1) = (r1, remainder);
+            (s0, s1) = (s1.clone(), s0 - quotient.clone() * s1);
+            (t0, t1) = (t1.clone(), t0 - quotient * t1);
+        }
+        let scale = Polynomial::new(vec![r0.coef.last().expect("Not both zero").clone().inv()]);
+        (r0 * scale.clone(), s0 * scale.clone(), t0 * scale)
+    }
+
+    /// self * other modulo `modulus`.
+    pub fn mul_mod(&self, other: &Self, modulus: &Self) -> Self {
+        (self.clone() * other.clone()).div_rem(modulus).1
+    }
+
+    /// self^exp modulo `modulus`, by square and multiply.
+    pub fn pow_mod(&self, exp: &BigUint, modulus: &Self) -> Self {
+        let one = Polynomial::new(vec![modulus.coef[0].one()]);
+        let mut result = one.div_rem(modulus).1;
+        let base = self.div_rem(modulus).1;
+        for i in (0..exp.bits()).rev() {
+            result = result.mul_mod(&result, modulus);
+            if exp.bit(i) {
+                result = result.mul_mod(&base, modulus);
+            }
+        }
+        result
+    }
+
+    /// self(inner(x)) modulo `modulus`, which keeps the intermediate degrees down.
+    pub fn compose_mod(&self, inner: &Self, modulus: &Self) -> Self {
+        let inner = inner.div_rem(modulus).1;
+        self.coef
+            .iter()
+            .rev()
+            .fold(Polynomial::new(vec![]), |acc, c| {
+                acc.mul_mod(&inner, modulus) + Polynomial::new(vec![c.clone()])
+            })
+            .div_rem(modulus)
+            .1
+    }
+
+    /// The polynomial of degree below n through n points with distinct x, as the sum of
+    /// y_i * l_i(x) / l_i(x_i) with l_i the product of x - x_j over j != i.
+    pub fn lagrange_interpolation(points: &[(F, F)]) -> Self {
+        let Some((sample, _)) = points.first() else {
+            return Polynomial::new(vec![]);
+        };
+        let linear = |x: &F| Polynomial::new(vec![-x.clone(), x.one()]);
+        let product = points
+            .iter()
+            .fold(Polynomial::new(vec![sample.one()]), |acc, (x, _)| {
+                acc * linear(x)
+            });
+        points.iter().fold(Polynomial::new(vec![]), |acc, (x, y)| {
+            let basis = product.div_rem(&linear(x)).0;
+            let scale = y.clone() * basis.evaluate(x).inv();
+            acc + basis * Polynomial::new(vec![scale])
+        })
+    }
+
+    /// Same polynomial as `lagrange_interpolation`, from the divided differences
+    /// c_i = [y_0, ..., y_i] as c_0 + (x - x_0) (c_1 + (x - x_1) (c_2 + ...)).
+    pub fn newton_interpolation(points: &[(F, F)]) -> Self {
+        let xs: Vec<F> = points.iter().map(|(x, _)| x.clone()).collect();
+        let mut differences: Vec<F> = points.iter().map(|(_, y)| y.clone()).collect();
+        for level in 1..points.len() {
+            for i in (level..points.len()).rev() {
+                let step = (xs[i].clone() - xs[i - level].clone()).inv();
+                differences[i] = (differences[i].clone() - differences[i - 1].clone()) * step;
+            }
+        }
+        xs.iter()
+            .zip(differences)
+            .rev()
+            .fold(Polynomial::new(vec![]), |acc, (x, c)| {
+                acc * Polynomial::new(vec![-x.clone(), x.one()]) + Polynomial::new(vec![c])
+            })
+    }
+}
@@ -0,0 +1,242 @@
+use num_bigint::BigUint;
+use rand::Rng;
+
+use super::Polynomial;
+use crate::{
+    T,
+    arithmetic::factor,
+    f2m::{
+        bit::Bits8,
+        limbs::{self, Modulus, degree, gcd, pow, square},
+    },
+    fp::Fp,
+};
+
+/// Largest coefficient tried in sparse polynomials over F_P.
+const SPARSE_COEFFICIENTS: T = 16;
+/// Steps of Ben-Or's test run before Rabin's test for dense moduli over GF(2).
+const BEN_OR_STEPS: usize = 32;
+
+impl<const P: T> Polynomial<Fp<P>> {
+    /// Ben-Or's test: f of degree n is irreducible when gcd(x^(P^i) - x, f) = 1 for every
+    /// i <= n / 2, as x^(P^i) - x is the product of all monic irreducibles of degree
+    /// dividing i. Reducible polynomials usually fail after a few steps.
+    pub fn is_irreducible(&self) -> bool {
+        let Some(n) = self.degree().filter(|&n| n > 0) else {
+            return false;
+        };
+        let f = self.monic();
+        let x = Self::new(vec![Fp::new(0), Fp::new(1)]);
+        let p = BigUint::from(P);
+        let mut power = x.div_rem(&f).1;
+        for _ in 0..n / 2 {
+            power = power.pow_mod(&p, &f);
+            if (power.clone() - x.clone()).gcd(&f).degree() != Some(0) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Irreducible f whose root x generates the multiplicative group of F_P[x] / (f).
+    /// Factors P^n - 1 by trial division, so it is meant for small fields.
+    pub fn is_primitive(&self) -> bool {
+        let Some(n) = self.degree() else {
+            return false;
+        };
+        let order = field_order(P, n) - 1;
+        self.is_irreducible() && self.generates(order, &factor(order))
+    }
+
+    /// Uniformly random monic irreducible polynomial of the given degree.
+    /// About one in `degree` monic polynomials is irreducible.
+    pub fn random_irreducible<R: Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        assert!(degree > 0, "Irreducible polynomials have a positive degree");
+        loop {
+            let mut coef: Vec<Fp<P>> = (0..degree)
+                .map(|_| Fp::new(rng.random_range(0..P)))
+                .collect();
+            coef.push(Fp::new(1));
+            let candidate = Self::new(coef);
+            if candidate.is_irreducible() {
+                return candidate;
+            }
+        }
+    }
+
+    /// Random monic primitive polynomial of the given degree, see `is_primitive`.
+    pub fn random_primitive<R: Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        let order = field_order(P, degree) - 1;
+        let factors = factor(order);
+        loop {
+            let candidate = Self::random_irreducible(degree, rng);
+            if candidate.generates(order, &factors) {
+                return candidate;
+            }
+        }
+    }
+
+    /// First irreducible x^n + a x^k + b, by k, a and b, or x^n + x^a + x^b + x^c + d,
+    /// by a, b, c and d, with coefficients below `SPARSE_COEFFICIENTS`.
+    /// Sparse moduli make reduction cheap. `None` if there is no such polynomial.
+    pub fn sparse_irreducible(degree: usize) -> Option<Self> {
+        assert!(degree > 1, "Sparse polynomials have a degree of at least 2");
+        let limit = P.min(SPARSE_COEFFICIENTS);
+        let monomial = |terms: &[(usize, T)]| {
+            let mut coef = vec![Fp::new(0); degree + 1];
+            coef[degree] = Fp::new(1);
+            for &(i, c) in terms {
+                coef[i] = Fp::new(c);
+            }
+            Self::new(coef)
+        };
+
+        let trinomials = (1..degree).flat_map(|k| {
+            (1..limit).flat_map(move |a| (1..limit).map(move |b| monomial(&[(k, a), (0, b)])))
+        });
+        let pentanomials = sparse_exponents(degree).flat_map(|(a, b, c)| {
+            (1..limit).map(move |d| monomial(&[(a, 1), (b, 1), (c, 1), (0, d)]))
+        });
+        trinomials
+            .chain(pentanomials)
+            .find(|candidate| candidate.is_irreducible())
+    }
+
+    /// x^(order / r) != 1 modulo self for every prime r dividing the order.
+    fn generates(&self, order: T, factors: &[(T, u32)]) -> bool {
+        let f = self.monic();
+        let x = Self::new(vec![Fp::new(0), Fp::new(1)]);
+        let one = Self::new(vec![Fp::new(1)]);
+        factors
+            .iter()
+            .all(|(r, _)| x.pow_mod(&BigUint::from(order / r), &f) != one)
+    }
+}
+
+/// Polynomials over GF(2) are handled as little-endian 64-bit words here,
+/// with bit i the coefficient of x^i.
+impl Polynomial<Bits8> {
+    /// Rabin's test: f of degree n is irreducible when x^(2^n) = x modulo f and
+    /// gcd(x^(2^(n / r)) - x, f) = 1 for the primes r dividing n. Squarings are cheap
+    /// over GF(2), so the gcds are left for the end, and for dense moduli, where
+    /// reductions are not, the first `BEN_OR_STEPS` steps of Ben-Or's test reject most
+    /// reducible polynomials early.
+    pub fn is_irreducible(&self) -> bool {
+        let words = to_words(self);
+        if degree(&words).unwrap_or(0) == 0 {
+            return false;
+        }
+        let f = Modulus::new(words);
+        let n = f.degree;
+        let rabin_steps: Vec<usize> = factor(n as T)
+            .into_iter()
+            .map(|(r, _)| n / r as usize)
+            .collect();
+        let x = f.rem(vec![0b10]);
+        let coprime = |power: &[u64]| {
+            let difference = power.iter().zip(&x).map(|(a, b)| a ^ b).collect();
+            degree(&gcd(difference, f.limbs.clone())) == Some(0)
+        };
+
+        let mut power = x.clone();
+        let mut rabin_powers = Vec::new();
+        for i in 1..=n {
+            power = f.rem(square(&power));
+            if f.terms.is_none() && i <= BEN_OR_STEPS.min(n / 2) && !coprime(&power) {
+                return false;
+            }
+            if rabin_steps.contains(&i) {
+                rabin_powers.push(power.clone());
+            }
+        }
+        power == x && rabin_powers.iter().all(|power| coprime(power))
+    }
+
+    /// Irreducible f whose root x generates the multiplicative group of GF(2^n).
+    /// Factors 2^n - 1 by trial division, so it is meant for degrees up to about 64.
+    pub fn is_primitive(&self) -> bool {
+        let Some(n) = self.degree() else {
+            return false;
+        };
+        let order = field_order(2, n) - 1;
+        self.is_irreducible() && self.generates(order, &factor(order))
+    }
+
+    /// Uniformly random irreducible polynomial of the given degree.
+    pub fn random_irreducible<R: Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        assert!(degree > 0, "Irreducible polynomials have a positive degree");
+        loop {
+            let mut words: Vec<u64> = (0..degree / 64 + 1).map(|_| rng.random()).collect();
+            words[degree / 64] &= (1 << (degree % 64)) - 1;
+            words[degree / 64] |= 1 << (degree % 64);
+            let candidate = from_words(&words);
+            if candidate.is_irreducible() {
+                return candidate;
+            }
+        }
+    }
+
+    /// Random primitive polynomial of the given degree, see `is_primitive`.
+    pub fn random_primitive<R: Rng + ?Sized>(degree: usize, rng: &mut R) -> Self {
+        let order = field_order(2, degree) - 1;
+        let factors = factor(order);
+        loop {
+            let candidate = Self::random_irreducible(degree, rng);
+            if candidate.generates(order, &factors) {
+                return candidate;
+            }
+        }
+    }
+
+    /// The trinomial x^n + x^k + 1 with the smallest k or, when there is none, the
+    /// pentanomial x^n + x^a + x^b + x^c + 1 with the smallest a, b and c, as in the
+    /// reduction polynomials of the standard binary curves. By Swan's theorem there are
+    /// no irreducible trinomials when 8 divides the degree.
+    pub fn sparse_irreducible(degree: usize) -> Option<Self> {
+        assert!(degree > 1, "Sparse polynomials have a degree of at least 2");
+        let polynomial = |exponents: &[usize]| {
+            let mut words = vec![0; degree / 64 + 1];
+            for &i in exponents.iter().chain([&0, &degree]) {
+                words[i / 64] |= 1 << (i % 64);
+            }
+            from_words(&words)
+        };
+        let trinomials = (1..degree)
+            .filter(|_| !degree.is_multiple_of(8))
+            .map(|k| polynomial(&[k]));
+        let pentanomials = sparse_exponents(degree).map(|(a, b, c)| polynomial(&[a, b, c]));
+        trinomials
+            .chain(pentanomials)
+            .find(|candidate| candidate.is_irreducible())
+    }
+
+    /// x^(order / r) != 1 modulo self for every prime r dividing the order.
+    fn generates(&self, order: T, factors: &[(T, u32)]) -> bool {
+        let f = Modulus::new(to_words(self));
+        factors.iter().all(|(r, _)| {
+            let power = pow(&[0b10], order / r, &f);
+            degree(&power) != Some(0)
+        })
+    }
+}
+
+/// P^n, which has to fit in `T`.
+fn field_order(p: T, n: usize) -> T {
+    u32::try_from(n)
+        .ok()
+        .and_then(|n| p.checked_pow(n))
+        .expect("Order of the field does not fit in T")
+}
+
+/// (a, b, c) with n > a > b > c > 0, by a, then b, then c.
+fn sparse_exponents(n: usize) -> impl Iterator<Item = (usize, usize, usize)> {
+    (3..n).flat_map(|a| (2..a).flat_map(move |b| (1..b).map(move |c| (a, b, c))))
+}
+
+fn to_words(poly: &Polynomial<Bits8>) -> Vec<u64> {
+    limbs::from_bytes(&poly.coef)
+}
+
+fn from_words(words: &[u64]) -> Polynomial<Bits8> {
+    Polynomial::new(limbs::to_bytes(words))
+}
@@ -0,0 +1,138 @@
+//! Products of coefficient vectors: schoolbook for short operands, the number-theoretic
+//! transform when the field has the roots of unity for it and Karatsuba otherwise.
+
+use super::Field;
+use crate::T;
+
+/// Operands shorter than this are multiplied by schoolbook.
+const KARATSUBA_THRESHOLD: usize = 32;
+/// Operands at least this long go through the NTT, when the field allows it.
+const NTT_THRESHOLD: usize = 64;
+
+/// Coefficients of the product, with the method picked by the length of the shorter operand.
+pub(super) fn multiply<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    if a.len().min(b.len()) >= NTT_THRESHOLD
+        && let Some(product) = ntt_multiply(a, b)
+    {
+        return product;
+    }
+    karatsuba(a, b)
+}
+
+pub(super) fn schoolbook<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let mut result = vec![a[0].zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] = result[i + j].clone() + x.clone() * y.clone();
+        }
+    }
+    result
+}
+
+/// a = a0 + a1 x^h and b likewise, with a0 b1 + a1 b0 = (a0 + a1)(b0 + b1) - a0 b0 - a1 b1.
+pub(super) fn karatsuba<F: Field>(a: &[F], b: &[F]) -> Vec<F> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    let h = a.len().div_ceil(2);
+    let (a0, a1) = a.split_at(h);
+    let mut result = vec![a[0].zero(); a.len() + b.len() - 1];
+    if b.len() <= h {
+        // Too short to split, so b multiplies both halves of a
+        add_shifted(&mut result, &karatsuba(a0, b), 0);
+        add_shifted(&mut result, &karatsuba(a1, b), h);
+        return result;
+    }
+
+    let (b0, b1) = b.split_at(h);
+    let sum = |low: &[F], high: &[F]| {
+        let mut sum = low.to_vec();
+        add_shifted(&mut sum, high, 0);
+        sum
+    };
+    let (z0, z2) = (karatsuba(a0, b0), karatsuba(a1, b1));
+    let mut z1 = karatsuba(&sum(a0, a1), &sum(b0, b1));
+    for (i, x) in z0.iter().enumerate() {
+        z1[i] = z1[i].clone() - x.clone();
+    }
+    for (i, x) in z2.iter().enumerate() {
+        z1[i] = z1[i].clone() - x.clone();
+    }
+    add_shifted(&mut result, &z0, 0);
+    add_shifted(&mut result, &z1, h);
+    add_shifted(&mut result, &z2, 2 * h);
+    result
+}
+
+/// a += b * x^shift, with a long enough.
+fn add_shifted<F: Field>(a: &mut [F], b: &[F], shift: usize) {
+    for (i, x) in b.iter().enumerate() {
+        a[i + shift] = a[i + shift].clone() + x.clone();
+    }
+}
+
+/// Evaluates both operands at the powers of a root of unity of order n, a power of two
+/// above the degree of the product, multiplies pointwise and interpolates back.
+/// `None` when the field has no such root.
+pub(super) fn ntt_multiply<F: Field>(a: &[F], b: &[F]) -> Option<Vec<F>> {
+    let length = a.len() + b.len() - 1;
+    let log_n = length.next_power_of_two().trailing_zeros();
+    let root = a[0].root_of_unity(log_n)?;
+    let (zero, one) = (a[0].zero(), a[0].one());
+
+    let transform = |values: &[F]| {
+        let mut values = values.to_vec();
+        values.resize(1 << log_n, zero.clone());
+        ntt(&mut values, &root);
+        values
+    };
+    let mut product: Vec<F> = transform(a)
+        .into_iter()
+        .zip(transform(b))
+        .map(|(x, y)| x * y)
+        .collect();
+
+    // The inverse transform is the transform at root^-1 = root^(n - 1), divided by n,
+    // which lies in the prime field where `/` is the field division
+    ntt(&mut product, &root.clone().pow((1 << log_n) - 1));
+    let n = (0..log_n).fold(one.clone(), |n, _| n.clone() + n);
+    let n_inverse = one / n;
+    product.truncate(length);
+    Some(product.into_iter().map(|x| x * n_inverse.clone()).collect())
+}
+
+/// In-place iterative Cooley-Tukey: values[i] becomes the polynomial evaluated at root^i.
+fn ntt<F: Field>(values: &mut [F], root: &F) {
+    let n = values.len();
+    let bits = n.trailing_zeros();
+    for i in 1..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        // Powers of a root of order 2 * half
+        let step = root.clone().pow((n / (2 * half)) as T);
+        let twiddles: Vec<F> =
+            std::iter::successors(Some(root.one()), |w| Some(w.clone() * step.clone()))
+                .take(half)
+                .collect();
+        for block in values.chunks_mut(2 * half) {
+            let (low, high) = block.split_at_mut(half);
+            for ((u, v), w) in low.iter_mut().zip(high.iter_mut()).zip(&twiddles) {
+                let t = v.clone() * w.clone();
+                *v = u.clone() - t.clone();
+                *u = u.clone() + t;
+            }
+        }
+        half *= 2;
+    }
+}
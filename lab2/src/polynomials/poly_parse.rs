@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use super::{ParseErrors, Polynomial};
+use crate::{T, f2m::bit::Bits8, fp::Fp};
+
+/// Reads the notation of `Display`, such as `2*x^5 + x^3 + 10*x^2 + 4*x + 3`: terms in
+/// strictly decreasing degree joined by `+`, coefficients in 1..p written only when they
+/// are not 1, and x^0, x^1 written as the bare coefficient and `x`. Spaces between tokens
+/// are ignored. Gives the nonzero (exponent, coefficient) pairs, none for `0`.
+fn parse_terms(s: &str, p: T) -> Result<Vec<(usize, T)>, ParseErrors> {
+    let mut cursor = Cursor {
+        bytes: s.as_bytes(),
+        position: 0,
+    };
+    if cursor.at_end() {
+        return Err(ParseErrors::Empty);
+    }
+    if s.trim() == "0" {
+        return Ok(vec![]);
+    }
+
+    let mut terms: Vec<(usize, T)> = Vec::new();
+    loop {
+        cursor.skip_spaces();
+        let start = cursor.position;
+        let coefficient = cursor.number();
+        let has_x = match coefficient {
+            Some(_) if cursor.eat(b'*') => cursor.expect(b'x')?,
+            Some(_) => false,
+            None => cursor.expect(b'x')?,
+        };
+        let exponent = match has_x && cursor.eat(b'^') {
+            true => {
+                let position = cursor.position;
+                match cursor.number().ok_or_else(|| cursor.stuck())? {
+                    Ok(exponent) if exponent > 1 => usize::try_from(exponent)
+                        .map_err(|_| ParseErrors::NonCanonicalExponent(position))?,
+                    _ => return Err(ParseErrors::NonCanonicalExponent(position)),
+                }
+            }
+            false => has_x as usize,
+        };
+
+        let canonical = match coefficient {
+            None => true,
+            Some(Ok(c)) => c > has_x as T && c < p,
+            Some(Err(())) => false,
+        };
+        if !canonical {
+            return Err(ParseErrors::NonCanonicalCoefficient(start));
+        }
+        if terms.last().is_some_and(|&(last, _)| exponent >= last) {
+            return Err(ParseErrors::TermsOutOfOrder(start));
+        }
+        terms.push((exponent, coefficient.map_or(1, |c| c.unwrap_or(1))));
+
+        if cursor.at_end() {
+            return Ok(terms);
+        }
+        if !cursor.eat(b'+') {
+            return Err(ParseErrors::UnexpectedCharacter(cursor.position));
+        }
+        if cursor.at_end() {
+            return Err(ParseErrors::IncompleteTerm(cursor.position));
+        }
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Cursor<'_> {
+    fn skip_spaces(&mut self) {
+        while self.bytes.get(self.position) == Some(&b' ') {
+            self.position += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_spaces();
+        self.position == self.bytes.len()
+    }
+
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_spaces();
+        let found = self.bytes.get(self.position) == Some(&byte);
+        self.position += found as usize;
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<bool, ParseErrors> {
+        match self.eat(byte) {
+            true => Ok(true),
+            false => Err(self.stuck()),
+        }
+    }
+
+    /// Error for a missing token, depending on whether the input ran out.
+    fn stuck(&mut self) -> ParseErrors {
+        match self.at_end() {
+            true => ParseErrors::IncompleteTerm(self.position),
+            false => ParseErrors::UnexpectedCharacter(self.position),
+        }
+    }
+
+    /// Digits at the cursor, `Err` when they overflow or have a leading zero.
+    fn number(&mut self) -> Option<Result<T, ()>> {
+        self.skip_spaces();
+        let digits = self.bytes[self.position..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return None;
+        }
+        let text = &self.bytes[self.position..self.position + digits];
+        self.position += digits;
+        let value = text.iter().try_fold(0, |acc: T, &digit| {
+            acc.checked_mul(10)?.checked_add((digit - b'0') as T)
+        });
+        match value {
+            Some(value) if text[0] != b'0' || digits == 1 => Some(Ok(value)),
+            _ => Some(Err(())),
+        }
+    }
+}
+
+/// Splits `<element> mod <modulus>`, the notation of `{:#}` for field elements, into the
+/// element and the parsed modulus, with the positions of errors counted in `s`.
+pub(crate) fn split_modulus<Q>(s: &str) -> Result<(&str, Q), ParseErrors>
+where
+    Q: FromStr<Err = ParseErrors>,
+{
+    let (element, modulus) = s.split_once(" mod ").ok_or(ParseErrors::MissingModulus)?;
+    let offset = element.len() + " mod ".len();
+    let modulus = modulus
+        .parse()
+        .map_err(|e: ParseErrors| e.shifted(offset))?;
+    Ok((element, modulus))
+}
+
+impl<const P: T> FromStr for Polynomial<Fp<P>> {
+    type Err = ParseErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = parse_terms(s, P)?;
+        let mut coef = vec![Fp::new(0); terms.first().map_or(0, |&(degree, _)| degree + 1)];
+        for (exponent, c) in terms {
+            coef[exponent] = Fp::new(c);
+        }
+        Ok(Polynomial::new(coef))
+    }
+}
+
+impl FromStr for Polynomial<Bits8> {
+    type Err = ParseErrors;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = parse_terms(s, 2)?;
+        let mut coef = vec![Bits8(0); terms.first().map_or(0, |&(degree, _)| degree / 8 + 1)];
+        for (exponent, _) in terms {
+            coef[exponent / 8].0 |= 1 << (exponent % 8);
+        }
+        Ok(Polynomial::new(coef))
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
+use super::poly_multiplication::multiply;
 use super::{Field, Polynomial};
 use crate::{T, traits::Pow};
 
@@ -44,7 +45,8 @@ impl<T: Field> Polynomial<T> {
         }
     }
 
-    fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+    /// Long division with `/` on the coefficients, which backs the `/` and `%` operators.
+    fn long_division(&self, rhs: &Self) -> (Self, Self) {
         let mut remainder = self.clone();
         let divisor = rhs.clone();
 
@@ -58,7 +60,6 @@ impl<T: Field> Polynomial<T> {
 
         // While degree(remainder) >= degree(divisor)
         while let Some(rem_deg) = remainder.degree()
-            && rem_deg > 0
             && rem_deg >= rhs_deg
         {
             // scale = lead(remainder) / lead(divisor)
@@ -99,7 +100,7 @@ impl<T: Field> Display for Polynomial<T> {
                 write!(f, " + ")?;
             }
 
-            if *coef != coef.one() {
+            if *coef != coef.one() || i == 0 {
                 write!(f, "{}", coef)?;
             }
 
@@ -164,20 +165,7 @@ impl<T: Field> Mul for Polynomial<T> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        if self.coef.is_empty() || other.coef.is_empty() {
-            return Self { coef: vec![] };
-        }
-
-        let new_len = self.coef.len() + other.coef.len() - 1;
-        let mut result = vec![self.coef[0].zero(); new_len];
-
-        for (i, a_val) in self.coef.into_iter().enumerate() {
-            for (j, b_val) in other.coef.clone().into_iter().enumerate() {
-                result[i + j] = result[i + j].clone() + (a_val.clone() * b_val);
-            }
-        }
-
-        Self::new(result)
+        Self::new(multiply(&self.coef, &other.coef))
     }
 }
 
@@ -186,7 +174,7 @@ impl<T: Field> Div for Polynomial<T> {
 
     fn div(self, other: Self) -> Self {
         assert!(!other.is_zero(), "Division of Polynomial by zero");
-        self.div_rem(&other).0
+        self.long_division(&other).0
     }
 }
 
@@ -195,7 +183,7 @@ impl<T: Field> Rem for Polynomial<T> {
 
     fn rem(self, other: Self) -> Self {
         assert!(!other.is_zero(), "Reminder of Polynomial by zero");
-        self.div_rem(&other).1
+        self.long_division(&other).1
     }
 }
 
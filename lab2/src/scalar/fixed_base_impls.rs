@@ -0,0 +1,83 @@
+use super::{FixedBase, ScalarBits, ScalarMul, wnaf_impls::significant_bits};
+
+impl<G: ScalarMul> FixedBase<G> {
+    /// Table for scalars of at most `max_bits` bits, with 2^6 entries.
+    pub fn new(base: G, max_bits: u32) -> Self {
+        Self::with_teeth(base, max_bits, 6)
+    }
+
+    /// Table with 2^teeth entries. Every multiplication then costs about
+    /// `max_bits / teeth` doublings and as many additions.
+    pub fn with_teeth(base: G, max_bits: u32, teeth: u32) -> Self {
+        assert!(
+            (1..=16).contains(&teeth),
+            "Number of teeth must be in 1..=16"
+        );
+        let spacing = max_bits.div_ceil(teeth).max(1);
+
+        // The point at infinity cannot even be converted, it does not know its curve
+        let mut table = Vec::new();
+        if !base.is_identity() {
+            table.push(base.identity_repr());
+            let mut tooth = base.to_repr();
+            for j in 0..teeth {
+                if j > 0 {
+                    for _ in 0..spacing {
+                        tooth = base.double_repr(&tooth);
+                    }
+                }
+                // Indices with the top bit j are the previous ones plus 2^(j * spacing) base
+                for index in 0..1 << j {
+                    let entry = match index {
+                        0 => tooth.clone(),
+                        _ => base.add_repr(&table[index], &tooth),
+                    };
+                    table.push(entry);
+                }
+            }
+        }
+
+        Self {
+            base,
+            teeth,
+            spacing,
+            table,
+        }
+    }
+
+    pub fn base(&self) -> &G {
+        &self.base
+    }
+
+    /// Largest number of bits a scalar may have.
+    pub fn max_bits(&self) -> u32 {
+        self.teeth * self.spacing
+    }
+
+    /// `scalar * base` in variable time, only use it for public scalars.
+    pub fn mul<S: ScalarBits + ?Sized>(&self, scalar: &S) -> G {
+        let bits = significant_bits(scalar);
+        assert!(
+            bits <= self.max_bits(),
+            "Scalar has {bits} bits, the table covers {}",
+            self.max_bits()
+        );
+        if self.base.is_identity() {
+            return self.base.clone();
+        }
+
+        let mut result = self.base.identity_repr();
+        for i in (0..self.spacing).rev() {
+            result = self.base.double_repr(&result);
+            let index = (0..self.teeth)
+                .map(|j| i + j * self.spacing)
+                .enumerate()
+                .filter(|&(_, bit)| bit < bits && scalar.bit(bit))
+                .fold(0, |index, (j, _)| index | 1 << j);
+            if index != 0 {
+                result = self.base.add_repr(&result, &self.table[index]);
+            }
+        }
+        self.base.normalize(&result)
+    }
+}
@@ -0,0 +1,474 @@
+//! Scalars of any width and scalar multiplication: a Montgomery ladder whose operations and
+//! swaps do not depend on the scalar, and wNAF, comb tables and multi-scalar multiplication
+//! for public data.
+
+use num_bigint::BigUint;
+
+use crate::traits::{Field, Inverse};
+
+mod fixed_base_impls;
+mod multi_mul_impls;
+mod wnaf_impls;
+
+pub use multi_mul_impls::{multi_mul, pippenger, shamir, straus};
+pub use wnaf_impls::wnaf;
+
+/// Read-only view of the bits of a non-negative integer.
+pub trait ScalarBits {
+    /// Number of bits a scalar multiplication walks through.
+    /// For fixed width integers this is the width of the type, so it does not depend on the value.
+    fn bit_width(&self) -> u32;
+    /// Bit at `index`, counted from the least significant one.
+    fn bit(&self, index: u32) -> bool;
+}
+
+macro_rules! impl_scalar_bits {
+    ($($t:ty),*) => {
+        $(impl ScalarBits for $t {
+            fn bit_width(&self) -> u32 {
+                <$t>::BITS
+            }
+
+            fn bit(&self, index: u32) -> bool {
+                (self >> index) & 1 == 1
+            }
+        })*
+    };
+}
+
+impl_scalar_bits!(u8, u16, u32, u64, u128, usize);
+
+/// Little-endian limbs, as used by `FpMont`.
+impl<const N: usize> ScalarBits for [u64; N] {
+    fn bit_width(&self) -> u32 {
+        64 * N as u32
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        (self[index as usize / 64] >> (index % 64)) & 1 == 1
+    }
+}
+
+/// The width is the length of the value, pad the scalar to a fixed size if that matters.
+impl ScalarBits for BigUint {
+    fn bit_width(&self) -> u32 {
+        self.bits() as u32
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        BigUint::bit(self, index as u64)
+    }
+}
+
+/// Swap chosen by a mask instead of a branch, as the ladder needs for secret bits.
+pub trait ConditionalSwap {
+    /// Swaps the values when `mask` is all ones and keeps them when it is zero,
+    /// by XOR of the limbs or coordinates under the mask.
+    fn conditional_swap(&mut self, other: &mut Self, mask: u64);
+}
+
+/// All ones for `true`, zero for `false`.
+#[inline(always)]
+pub fn mask(bit: bool) -> u64 {
+    0_u64.wrapping_sub(bit as u64)
+}
+
+/// Group in which scalars multiply elements, written additively.
+/// For fields this is the multiplicative group, so adding multiplies and doubling squares.
+pub trait ScalarMul: Clone {
+    /// Form of intermediate results, e.g. projective coordinates for curve points.
+    type Repr: Clone + std::fmt::Debug + ConditionalSwap;
+
+    /// Whether the element is the identity of the group.
+    fn is_identity(&self) -> bool;
+    fn to_repr(&self) -> Self::Repr;
+    /// Converts back to an element, `self` only supplies the context, such as the curve.
+    fn normalize(&self, repr: &Self::Repr) -> Self;
+    /// Identity of the group `self` belongs to.
+    fn identity_repr(&self) -> Self::Repr;
+    fn add_repr(&self, a: &Self::Repr, b: &Self::Repr) -> Self::Repr;
+    fn double_repr(&self, a: &Self::Repr) -> Self::Repr;
+    /// Inverse of the element in the group.
+    fn negate(&self) -> Self;
+
+    /// Scalar multiplication for secret scalars by `montgomery_ladder`, whose group
+    /// operations and swaps do not depend on the scalar.
+    fn ladder<S: ScalarBits + ?Sized>(&self, scalar: &S) -> Self {
+        montgomery_ladder(self, scalar)
+    }
+
+    /// Width-w NAF with precomputed odd multiples. Much faster than the ladder,
+    /// but the running time depends on the scalar, so only use it for public data.
+    fn mul_vartime<S: ScalarBits + ?Sized>(&self, scalar: &S) -> Self {
+        wnaf_impls::mul_vartime(self, scalar)
+    }
+}
+
+impl<F: Field + Inverse + ConditionalSwap> ScalarMul for F {
+    type Repr = F;
+
+    fn is_identity(&self) -> bool {
+        *self == self.one()
+    }
+
+    fn to_repr(&self) -> F {
+        self.clone()
+    }
+
+    fn normalize(&self, repr: &F) -> F {
+        repr.clone()
+    }
+
+    fn identity_repr(&self) -> F {
+        self.one()
+    }
+
+    fn add_repr(&self, a: &F, b: &F) -> F {
+        a.clone() * b.clone()
+    }
+
+    fn double_repr(&self, a: &F) -> F {
+        a.clone() * a.clone()
+    }
+
+    /// Zero has no inverse, but it absorbs every product, so it stands in for its own.
+    fn negate(&self) -> F {
+        match *self == self.zero() {
+            true => self.clone(),
+            false => self.clone().inv(),
+        }
+    }
+
+    /// Multiplying by one costs what any other product does, so the ladder starts from
+    /// (1, base) and needs no offset. That also keeps it free of inverses, which elements
+    /// of a ring with a reducible modulus may lack.
+    fn ladder<S: ScalarBits + ?Sized>(&self, scalar: &S) -> F {
+        ladder_steps(self, self.one(), self.clone(), scalar)
+    }
+}
+
+/// Precomputed comb table (Lim-Lee) for a base that is multiplied over and over,
+/// such as the generator of a group.
+#[derive(Debug, Clone)]
+pub struct FixedBase<G: ScalarMul> {
+    base: G,
+    /// Number of bits combined into one table index.
+    teeth: u32,
+    /// Distance between the bits of one index, `teeth * spacing` bits are covered.
+    spacing: u32,
+    /// Sums of `2^(j * spacing) * base` over the bits `j` of the index.
+    table: Vec<G::Repr>,
+}
+
+/// Montgomery ladder keeping the invariant r1 = r0 + base, for k of width w.
+///
+/// It starts from (base, 2 base) as if k had a further top bit, which gives
+/// (2^w + k) base, and adds -2^w base at the end. Every bit costs a masked swap, one
+/// addition and one doubling, and the offset w more doublings, whatever the value of k.
+/// The operands are m base and (m + 1) base for the leading bits m of 2^w + k, so the
+/// identity is never one of them while the order of the base exceeds 2^(w + 1), and only
+/// for a negligible share of scalars in groups of cryptographic size.
+/// The time of the field arithmetic itself is up to the field type.
+pub fn montgomery_ladder<G: ScalarMul, S: ScalarBits + ?Sized>(base: &G, scalar: &S) -> G {
+    let b = base.to_repr();
+    let r0 = ladder_steps(base, b.clone(), base.double_repr(&b), scalar);
+    let offset = (0..scalar.bit_width()).fold(base.negate().to_repr(), |d, _| base.double_repr(&d));
+    base.normalize(&base.add_repr(&r0, &offset))
+}
+
+/// The bits of the ladder from (r0, r1) = (m base, (m + 1) base), giving (2^w m + k) base.
+fn ladder_steps<G: ScalarMul, S: ScalarBits + ?Sized>(
+    base: &G,
+    mut r0: G::Repr,
+    mut r1: G::Repr,
+    scalar: &S,
+) -> G::Repr {
+    let mut swapped = false;
+    for i in (0..scalar.bit_width()).rev() {
+        let bit = scalar.bit(i);
+        r0.conditional_swap(&mut r1, mask(bit ^ swapped));
+        swapped = bit;
+        r1 = base.add_repr(&r0, &r1);
+        r0 = base.double_repr(&r0);
+    }
+    r0.conditional_swap(&mut r1, mask(swapped));
+    r0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        T,
+        elliptic_curve::{Ec, EcPoint},
+        f2m::{F2m, bit::Bits8},
+        fp::Fp,
+        fpk::Fpk,
+        polynomials::Polynomial,
+        traits::Pow,
+    };
+
+    const P: T = 1_000_000_007;
+
+    #[test]
+    fn test_matches_pow() {
+        let a: Fp<P> = Fp::new(123_456_789);
+        let pk: Polynomial<Fp<19>> = Polynomial::new_from_slice(&[2, 0, 1]);
+        let b: Fpk<19, 2> = Fpk::new(Polynomial::new_from_slice(&[3, 7]), pk);
+        let c: F2m<5> = F2m::new(
+            Polynomial::new(vec![Bits8(0b10110)]),
+            Polynomial::new(vec![Bits8(0b100101)]),
+        );
+
+        for exp in [0_u128, 1, 2, 3, 1000, 0xdead_beef, u128::MAX] {
+            assert_eq!(a.ladder(&exp), a.pow(exp));
+            assert_eq!(b.ladder(&exp), b.clone().pow(exp));
+            assert_eq!(c.ladder(&exp), c.clone().pow(exp));
+        }
+        assert_eq!(a.ladder(&5_u8), a.pow(5));
+        assert_eq!(a.ladder(&[5_u64, 0]), a.pow(5));
+    }
+
+    thread_local! {
+        static TRACE: std::cell::RefCell<Vec<&'static str>> = const { std::cell::RefCell::new(vec![]) };
+    }
+
+    /// Multiplicative group of F_P that records every operation the ladder performs.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Traced(Fp<P>);
+
+    impl Traced {
+        fn record(&self, operation: &'static str, operands: &[&Traced]) {
+            let identity = operands.iter().any(|x| x.is_identity());
+            TRACE
+                .with_borrow_mut(|trace| trace.push(if identity { "identity" } else { operation }));
+        }
+    }
+
+    impl ConditionalSwap for Traced {
+        fn conditional_swap(&mut self, other: &mut Self, mask: u64) {
+            self.record("swap", &[]);
+            self.0.conditional_swap(&mut other.0, mask);
+        }
+    }
+
+    impl ScalarMul for Traced {
+        type Repr = Traced;
+
+        fn is_identity(&self) -> bool {
+            self.0 == self.0.one()
+        }
+        fn to_repr(&self) -> Traced {
+            self.clone()
+        }
+        fn normalize(&self, repr: &Traced) -> Traced {
+            repr.clone()
+        }
+        fn identity_repr(&self) -> Traced {
+            Traced(self.0.one())
+        }
+        fn add_repr(&self, a: &Traced, b: &Traced) -> Traced {
+            self.record("add", &[a, b]);
+            Traced(a.0 * b.0)
+        }
+        fn double_repr(&self, a: &Traced) -> Traced {
+            self.record("double", &[a]);
+            Traced(a.0 * a.0)
+        }
+        fn negate(&self) -> Traced {
+            Traced(self.0.inv())
+        }
+    }
+
+    #[test]
+    fn test_ladder_trace() {
+        // 5 has order at least (P - 1) / 2, well above 2^9, so no operand is the identity
+        let base = Traced(Fp::new(5));
+        let mut traces = (0..=255_u8).map(|k| {
+            TRACE.with_borrow_mut(|trace| trace.clear());
+            assert_eq!(base.ladder(&k), Traced(base.0.pow(k as T)));
+            TRACE.with_borrow_mut(std::mem::take)
+        });
+
+        let first = traces.next().unwrap();
+        assert_eq!(first.iter().filter(|&&op| op == "swap").count(), 9);
+        assert!(!first.contains(&"identity"));
+        assert!(traces.all(|trace| trace == first));
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        let (mut a, mut b): (Fp<P>, Fp<P>) = (Fp::new(3), Fp::new(P - 1));
+        a.conditional_swap(&mut b, mask(false));
+        assert_eq!((a, b), (Fp::new(3), Fp::new(P - 1)));
+        a.conditional_swap(&mut b, mask(true));
+        assert_eq!((a, b), (Fp::new(P - 1), Fp::new(3)));
+
+        let pk: Polynomial<Fp<19>> = Polynomial::new_from_slice(&[2, 0, 1]);
+        let mut c: Fpk<19, 2> = Fpk::new(Polynomial::new_from_slice(&[3, 7]), pk.clone());
+        let mut d: Fpk<19, 2> = Fpk::new(Polynomial::new_from_slice(&[5]), pk);
+        let (c0, d0) = (c.clone(), d.clone());
+        c.conditional_swap(&mut d, mask(true));
+        assert_eq!((c, d), (d0, c0));
+    }
+
+    #[test]
+    fn test_wide_scalars() {
+        // a^(P - 1) = 1, so a multiple of P - 1 added to the exponent changes nothing
+        let a: Fp<P> = Fp::new(987_654_321);
+        let wide: BigUint = (BigUint::from(P - 1) << 200) + 12345_u32;
+        let limbs: [u64; 4] = {
+            let mut limbs = [0; 4];
+            limbs[..wide.to_u64_digits().len()].copy_from_slice(&wide.to_u64_digits());
+            limbs
+        };
+
+        assert_eq!(a.ladder(&wide), a.pow(12345));
+        assert_eq!(a.ladder(&limbs), a.pow(12345));
+        assert_eq!(a.ladder(&BigUint::ZERO), a.one());
+    }
+
+    #[test]
+    fn test_ec_points() {
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let point = EcPoint::new(Fp::new(5), Fp::new(1), ec).unwrap();
+
+        // The group has 19 points
+        for k in 0..40_u128 {
+            assert_eq!(point.ladder(&k), point.clone().pow(k));
+        }
+        assert_eq!(
+            point.ladder(&((BigUint::from(19_u8) << 150) + 7_u8)),
+            point.clone() * 7
+        );
+        assert_eq!(EcPoint::<Fp<Q>>::Infinity.ladder(&5_u32), EcPoint::Infinity);
+    }
+
+    #[test]
+    fn test_wnaf_digits() {
+        for k in [0_u128, 1, 7, 255, 1000, 0xdead_beef, u128::MAX] {
+            for width in 2..=6 {
+                let digits = wnaf(&k, width);
+                let value = digits
+                    .iter()
+                    .rev()
+                    .fold(BigUint::ZERO, |acc, &d| match d < 0 {
+                        true => acc * 2_u8 - d.unsigned_abs(),
+                        false => acc * 2_u8 + d as u64,
+                    });
+                assert_eq!(value, BigUint::from(k));
+
+                for (i, &d) in digits.iter().enumerate().filter(|(_, d)| **d != 0) {
+                    assert_eq!(d % 2, d.signum());
+                    assert!(d.unsigned_abs() < 1 << (width - 1));
+                    let next = &digits[i + 1..digits.len().min(i + width as usize)];
+                    assert!(next.iter().all(|&d| d == 0));
+                }
+            }
+        }
+        assert_eq!(wnaf(&7_u8, 2), vec![-1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_mul_vartime() {
+        let a: Fp<P> = Fp::new(123_456_789);
+        let pk: Polynomial<Fp<19>> = Polynomial::new_from_slice(&[2, 0, 1]);
+        let b: Fpk<19, 2> = Fpk::new(Polynomial::new_from_slice(&[3, 7]), pk);
+
+        for exp in [0_u128, 1, 2, 3, 1000, 0xdead_beef, u128::MAX] {
+            assert_eq!(a.mul_vartime(&exp), a.pow(exp));
+            assert_eq!(b.mul_vartime(&exp), b.clone().pow(exp));
+        }
+        let wide: BigUint = (BigUint::from(P - 1) << 700) + 12345_u32;
+        assert_eq!(a.mul_vartime(&wide), a.pow(12345));
+        assert_eq!(a.zero().mul_vartime(&7_u8), a.zero());
+        assert_eq!(a.zero().mul_vartime(&0_u8), a.one());
+
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let point = EcPoint::new(Fp::new(5), Fp::new(1), ec).unwrap();
+        for k in 0..40_u128 {
+            assert_eq!(point.mul_vartime(&k), point.clone().pow(k));
+        }
+        assert_eq!(
+            EcPoint::<Fp<Q>>::Infinity.mul_vartime(&5_u32),
+            EcPoint::Infinity
+        );
+    }
+
+    #[test]
+    fn test_fixed_base() {
+        let a: Fp<P> = Fp::new(987_654_321);
+        for teeth in [1, 3, 6] {
+            let table = FixedBase::with_teeth(a, 64, teeth);
+            assert!(table.max_bits() >= 64);
+            for exp in [0_u64, 1, 2, 1000, 0xdead_beef, u64::MAX] {
+                assert_eq!(table.mul(&exp), a.pow(exp as T));
+            }
+        }
+
+        let c: F2m<5> = F2m::new(
+            Polynomial::new(vec![Bits8(0b10110)]),
+            Polynomial::new(vec![Bits8(0b100101)]),
+        );
+        let table = FixedBase::new(c.clone(), 128);
+        assert_eq!(table.mul(&u128::MAX), c.clone().pow(u128::MAX));
+
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let point = EcPoint::new(Fp::new(5), Fp::new(1), ec).unwrap();
+        let table = FixedBase::new(point.clone(), 8);
+        for k in 0..=255_u8 {
+            assert_eq!(table.mul(&k), point.clone().pow(k as T));
+        }
+        let table = FixedBase::new(EcPoint::<Fp<Q>>::Infinity, 8);
+        assert_eq!(table.mul(&5_u8), EcPoint::Infinity);
+    }
+
+    #[test]
+    fn test_multi_mul() {
+        let a: Fp<P> = Fp::new(987_654_321);
+        const Q: T = 17;
+        let ec: Ec<Fp<Q>> = Ec::new(Fp::new(2), Fp::new(2));
+        let point = EcPoint::new(Fp::new(5), Fp::new(1), ec).unwrap();
+
+        for n in [1, 2, 3, 10, 40, 200] {
+            let scalars: Vec<u64> = (0..n as u64)
+                .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (i % 40))
+                .collect();
+            let elements: Vec<Fp<P>> = (1..=n as T).map(|i| a.pow(i)).collect();
+            let points: Vec<_> = (0..n as T).map(|i| point.clone().pow(i)).collect();
+
+            let expected = elements
+                .iter()
+                .zip(&scalars)
+                .fold(a.one(), |acc, (g, k)| acc * g.pow(*k as T));
+            let expected_point = points
+                .iter()
+                .zip(&scalars)
+                .fold(EcPoint::Infinity, |acc, (g, k)| {
+                    acc + g.clone().pow(*k as T)
+                });
+
+            assert_eq!(multi_mul(&elements, &scalars), Some(expected));
+            assert_eq!(straus(&elements, &scalars), Some(expected));
+            assert_eq!(pippenger(&elements, &scalars), Some(expected));
+            assert_eq!(multi_mul(&points, &scalars), Some(expected_point.clone()));
+            assert_eq!(straus(&points, &scalars), Some(expected_point.clone()));
+            assert_eq!(pippenger(&points, &scalars), Some(expected_point));
+        }
+
+        let wide: BigUint = (BigUint::from(P - 1) << 200) + 12345_u32;
+        assert_eq!(shamir((&a, &wide), (&a, &5_u8)), a.pow(12350));
+        let infinity = EcPoint::Infinity;
+        assert_eq!(
+            shamir((&point, &3_u8), (&infinity, &5_u8)),
+            point.clone() * 3
+        );
+        let infinities = [infinity.clone(), infinity.clone()];
+        assert_eq!(multi_mul(&infinities, &[5_u8, 7]), Some(infinity.clone()));
+        assert_eq!(pippenger(&infinities, &[5_u8, 7]), Some(infinity));
+        assert_eq!(multi_mul::<Fp<P>, u8>(&[], &[]), None);
+    }
+}
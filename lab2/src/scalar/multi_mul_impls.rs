@@ -0,0 +1,161 @@
+use super::{
+    ScalarBits, ScalarMul,
+    wnaf_impls::{odd_multiples, significant_bits, window_width, wnaf},
+};
+
+/// Up to this many terms Straus is used, above it Pippenger.
+const STRAUS_MAX_TERMS: usize = 32;
+
+/// Sum of `scalars[i] * bases[i]`, picking the algorithm by the number of terms.
+/// All bases must belong to the same group. Runs in variable time, so only use it for
+/// public scalars. Returns `None` when there are no terms, as there is no group to
+/// take the identity from.
+pub fn multi_mul<G: ScalarMul, S: ScalarBits>(bases: &[G], scalars: &[S]) -> Option<G> {
+    assert_eq!(bases.len(), scalars.len(), "Every base needs a scalar");
+    match bases.len() {
+        2 => Some(shamir((&bases[0], &scalars[0]), (&bases[1], &scalars[1]))),
+        n if n <= STRAUS_MAX_TERMS => straus(bases, scalars),
+        _ => pippenger(bases, scalars),
+    }
+}
+
+/// Shamir's trick: one pass over the bits of both scalars with the table {P, Q, P + Q}.
+/// Runs in variable time, so only use it for public scalars.
+pub fn shamir<G, S1, S2>((p, k1): (&G, &S1), (q, k2): (&G, &S2)) -> G
+where
+    G: ScalarMul,
+    S1: ScalarBits + ?Sized,
+    S2: ScalarBits + ?Sized,
+{
+    let (base, p, q) = match (p.is_identity(), q.is_identity()) {
+        (true, true) => return p.clone(),
+        (true, false) => return q.mul_vartime(k2),
+        (false, true) => return p.mul_vartime(k1),
+        (false, false) => (p, p.to_repr(), q.to_repr()),
+    };
+    let table = [base.add_repr(&p, &q), p, q];
+
+    let (bits1, bits2) = (significant_bits(k1), significant_bits(k2));
+    let mut result = base.identity_repr();
+    for i in (0..bits1.max(bits2)).rev() {
+        result = base.double_repr(&result);
+        let entry = match (i < bits1 && k1.bit(i), i < bits2 && k2.bit(i)) {
+            (true, true) => &table[0],
+            (true, false) => &table[1],
+            (false, true) => &table[2],
+            (false, false) => continue,
+        };
+        result = base.add_repr(&result, entry);
+    }
+    base.normalize(&result)
+}
+
+/// Straus: interleaved wNAF, every term gets its own table but the doublings are shared.
+pub fn straus<G: ScalarMul, S: ScalarBits>(bases: &[G], scalars: &[S]) -> Option<G> {
+    assert_eq!(bases.len(), scalars.len(), "Every base needs a scalar");
+    let (base, terms) = non_trivial_terms(bases, scalars)?;
+    if terms.is_empty() {
+        return Some(base.clone());
+    }
+
+    let tables: Vec<_> = terms
+        .iter()
+        .map(|(g, k)| {
+            let width = window_width(significant_bits(*k));
+            let digits = wnaf(*k, width);
+            let negative = match digits.iter().any(|&d| d < 0) {
+                true => odd_multiples(&g.negate(), width),
+                false => Vec::new(),
+            };
+            (digits, odd_multiples(*g, width), negative)
+        })
+        .collect();
+
+    let length = tables.iter().map(|(d, ..)| d.len()).max().unwrap_or(0);
+    let mut result = base.identity_repr();
+    for i in (0..length).rev() {
+        result = base.double_repr(&result);
+        for (digits, positive, negative) in &tables {
+            match digits.get(i).copied().unwrap_or(0) {
+                0 => {}
+                d if d > 0 => result = base.add_repr(&result, &positive[d as usize / 2]),
+                d => result = base.add_repr(&result, &negative[d.unsigned_abs() as usize / 2]),
+            }
+        }
+    }
+    Some(base.normalize(&result))
+}
+
+/// Pippenger's bucket method. Scalars are cut into windows of c bits, for every window
+/// the bases are sorted into 2^c buckets by their digit, and the buckets are summed
+/// with weights 1..2^c using two running sums. Pays off for hundreds of terms.
+pub fn pippenger<G: ScalarMul, S: ScalarBits>(bases: &[G], scalars: &[S]) -> Option<G> {
+    assert_eq!(bases.len(), scalars.len(), "Every base needs a scalar");
+    let (base, terms) = non_trivial_terms(bases, scalars)?;
+    if terms.is_empty() {
+        return Some(base.clone());
+    }
+
+    let window = (usize::BITS - terms.len().leading_zeros())
+        .saturating_sub(2)
+        .clamp(2, 16);
+    let bits = terms
+        .iter()
+        .map(|(_, k)| significant_bits(*k))
+        .max()
+        .unwrap_or(0);
+    let reprs: Vec<_> = terms.iter().map(|(g, _)| g.to_repr()).collect();
+    let add = |a: Option<G::Repr>, b: &G::Repr| match a {
+        Some(a) => base.add_repr(&a, b),
+        None => b.clone(),
+    };
+
+    let mut result = base.identity_repr();
+    for w in (0..bits.div_ceil(window)).rev() {
+        for _ in 0..window {
+            result = base.double_repr(&result);
+        }
+
+        let mut buckets: Vec<Option<G::Repr>> = vec![None; (1 << window) - 1];
+        for ((_, k), repr) in terms.iter().zip(&reprs) {
+            let digit = (0..window)
+                .map(|j| w * window + j)
+                .filter(|&i| i < k.bit_width() && k.bit(i))
+                .fold(0, |digit, i| digit | 1 << (i - w * window));
+            if digit != 0 {
+                let bucket = buckets[digit - 1].take();
+                buckets[digit - 1] = Some(add(bucket, repr));
+            }
+        }
+
+        // sum_b b * bucket[b] as the sum of all suffix sums
+        let mut suffix: Option<G::Repr> = None;
+        for bucket in buckets.iter().rev() {
+            if let Some(bucket) = bucket {
+                suffix = Some(add(suffix, bucket));
+            }
+            if let Some(suffix) = &suffix {
+                result = base.add_repr(&result, suffix);
+            }
+        }
+    }
+    Some(base.normalize(&result))
+}
+
+/// Drops the terms whose base is the identity, and returns one of the remaining
+/// bases as the context for the arithmetic. If all of them are dropped, the context
+/// is the identity itself, and `None` is only returned for no terms at all.
+fn non_trivial_terms<'a, G: ScalarMul, S: ScalarBits>(
+    bases: &'a [G],
+    scalars: &'a [S],
+) -> Option<(&'a G, Vec<(&'a G, &'a S)>)> {
+    let terms: Vec<_> = bases
+        .iter()
+        .zip(scalars)
+        .filter(|(g, _)| !g.is_identity())
+        .collect();
+    match terms.first() {
+        Some(&(base, _)) => Some((base, terms)),
+        None => bases.first().map(|g| (g, terms)),
+    }
+}
@@ -0,0 +1,97 @@
+use num_bigint::BigUint;
+
+use super::{ScalarBits, ScalarMul};
+
+/// Width-w non-adjacent form of the scalar, least significant digit first.
+/// Non-zero digits are odd, below 2^(width - 1) in absolute value,
+/// and any `width` consecutive digits contain at most one of them.
+pub fn wnaf<S: ScalarBits + ?Sized>(scalar: &S, width: u32) -> Vec<i64> {
+    assert!((2..=32).contains(&width), "Window width must be in 2..=32");
+    let modulus = 1_i64 << width;
+    let mut k = to_biguint(scalar);
+    let mut digits = Vec::with_capacity(k.bits() as usize + 1);
+
+    while k != BigUint::ZERO {
+        let mut digit = 0;
+        if k.bit(0) {
+            digit = (k.iter_u64_digits().next().unwrap_or(0) as i64) & (modulus - 1);
+            if digit >= modulus / 2 {
+                digit -= modulus;
+            }
+            match digit > 0 {
+                true => k -= digit as u64,
+                false => k += digit.unsigned_abs(),
+            }
+        }
+        digits.push(digit);
+        k >>= 1;
+    }
+
+    digits
+}
+
+/// Number of bits up to and including the highest set one.
+pub(super) fn significant_bits<S: ScalarBits + ?Sized>(scalar: &S) -> u32 {
+    (0..scalar.bit_width())
+        .rev()
+        .find(|&i| scalar.bit(i))
+        .map_or(0, |i| i + 1)
+}
+
+fn to_biguint<S: ScalarBits + ?Sized>(scalar: &S) -> BigUint {
+    let mut k = BigUint::ZERO;
+    for i in 0..significant_bits(scalar) {
+        if scalar.bit(i) {
+            k.set_bit(i as u64, true);
+        }
+    }
+    k
+}
+
+/// Window width balancing the size of the table against the number of additions.
+pub(super) fn window_width(bits: u32) -> u32 {
+    match bits {
+        0..=24 => 2,
+        25..=80 => 3,
+        81..=240 => 4,
+        241..=640 => 5,
+        _ => 6,
+    }
+}
+
+/// Odd multiples base, 3 base, ..., (2^(width - 1) - 1) base.
+pub(super) fn odd_multiples<G: ScalarMul>(base: &G, width: u32) -> Vec<G::Repr> {
+    let first = base.to_repr();
+    let twice = base.double_repr(&first);
+    let mut multiples = vec![first];
+    for _ in 1..1 << (width - 2) {
+        let next = base.add_repr(multiples.last().unwrap(), &twice);
+        multiples.push(next);
+    }
+    multiples
+}
+
+pub(super) fn mul_vartime<G: ScalarMul, S: ScalarBits + ?Sized>(base: &G, scalar: &S) -> G {
+    if base.is_identity() {
+        return base.clone();
+    }
+
+    let width = window_width(significant_bits(scalar));
+    let digits = wnaf(scalar, width);
+    let positive = odd_multiples(base, width);
+    let negative = match digits.iter().any(|&d| d < 0) {
+        true => odd_multiples(&base.negate(), width),
+        false => Vec::new(),
+    };
+
+    let mut result = base.identity_repr();
+    for &digit in digits.iter().rev() {
+        result = base.double_repr(&result);
+        if digit > 0 {
+            result = base.add_repr(&result, &positive[digit as usize / 2]);
+        } else if digit < 0 {
+            result = base.add_repr(&result, &negative[digit.unsigned_abs() as usize / 2]);
+        }
+    }
+    base.normalize(&result)
+}
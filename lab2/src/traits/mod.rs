@@ -1,8 +1,12 @@
 use std::fmt::Display;
+use std::hash::Hash;
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
+use num_bigint::BigUint;
+
 use crate::T;
-use crate::elliptic_curve::{EcErrors, EcPoint};
+use crate::elliptic_curve::{Ec, EcErrors, EcPoint};
+use crate::scalar::ConditionalSwap;
 
 pub mod needed_impls;
 
@@ -22,6 +26,11 @@ pub trait Field:
     + Display
     + Pow
 {
+    /// A primitive 2^log_order-th root of unity, which lets polynomial multiplication use
+    /// the number-theoretic transform. `None` when the field has none or it is not known.
+    fn root_of_unity(&self, _log_order: u32) -> Option<Self> {
+        None
+    }
 }
 
 pub trait Pow: std::ops::Mul<Output = Self> + Clone {
@@ -45,6 +54,43 @@ pub trait Pow: std::ops::Mul<Output = Self> + Clone {
 
         result
     }
+
+    /// Same as `pow`, for exponents that do not fit in `T`.
+    fn pow_big(self, exp: &BigUint) -> Self {
+        (0..exp.bits()).rev().fold(self.one(), |result, i| {
+            let result = result.clone() * result;
+            if exp.bit(i) {
+                result * self.clone()
+            } else {
+                result
+            }
+        })
+    }
+}
+
+/// Field with a finite number of elements, which hash by value so that tables can be
+/// keyed by them.
+pub trait FiniteField: Field + Hash {
+    /// Number of elements of the field.
+    fn order(&self) -> BigUint;
+
+    /// The `index`-th element of a fixed enumeration of the field, with zero at index 0.
+    /// Indices must be smaller than the order.
+    fn element_at(&self, index: T) -> Self;
+
+    /// Returns whether the element is a k-th power of some element of the field.
+    /// Uses the fact that the multiplicative group is cyclic of order q - 1.
+    fn is_kth_power(&self, k: T) -> bool {
+        if *self == self.zero() {
+            return true;
+        }
+        let q_minus_one = self.order() - 1_u32;
+        let (mut a, mut b) = (k, (&q_minus_one % k).try_into().unwrap_or(0));
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        self.clone().pow_big(&(q_minus_one / a)) == self.one()
+    }
 }
 
 pub trait Inverse {
@@ -55,9 +101,74 @@ pub trait Sqrt: Field {
     fn sqrt(self) -> Option<Self>;
 }
 
+/// Tells an element of odd characteristic apart from its negation,
+/// which is how one of the two square roots gets picked.
+pub trait Parity {
+    fn is_odd(&self) -> bool;
+}
+
+/// Fixed length big-endian encoding of field elements, as used by SEC1.
+pub trait FieldBytes: Field {
+    /// Length of every encoding, given by the size of the field.
+    fn byte_len(&self) -> usize;
+    fn to_fixed_bytes(&self) -> Vec<u8>;
+    /// Element of the same field as `self`, `None` unless `bytes` is a canonical encoding.
+    fn parse_fixed_bytes(&self, bytes: &[u8]) -> Option<Self>;
+}
+
 pub trait EcCalculations<T: Field> {
     fn is_point_on_curve(&self, point: &EcPoint<T>) -> bool;
-    fn get_point_on_curve(&self, x: T) -> Result<EcPoint<T>, EcErrors>;
+    /// Lifts x to a point on the curve. Of the two possible points `y_bit` picks the one
+    /// whose compressed form (as in SEC1) has that bit: the parity of y for odd characteristic
+    /// and the lowest bit of y / x for binary curves.
+    fn get_point_on_curve(&self, x: T, y_bit: bool) -> Result<EcPoint<T>, EcErrors>;
+    /// The bit of the compressed form of (x, y), as taken by `get_point_on_curve`.
+    fn compression_bit(&self, x: &T, y: &T) -> bool;
     fn add_points(&self, p1: (T, T), p2: (T, T)) -> EcPoint<T>;
     fn double_point(&self, p: (T, T)) -> EcPoint<T>;
+    /// Discriminant of the curve, zero exactly when the curve is singular.
+    fn discriminant(&self) -> T;
+    /// j-invariant of the curve, `None` for singular curves.
+    fn j_invariant(&self) -> Option<T>;
+    /// Returns whether the curves are isomorphic over the base field.
+    /// Singular curves are not isomorphic to anything.
+    fn is_isomorphic(&self, other: &Self) -> bool;
+    /// Quadratic twist of the curve by `d`, which must be a non-square
+    /// for odd characteristic and an element of trace one for binary curves.
+    fn quadratic_twist(&self, d: T) -> Ec<T>;
+    /// Some element that `quadratic_twist` accepts.
+    fn twist_parameter(&self) -> T;
+}
+
+/// Inversion-free point arithmetic on a curve.
+/// Only converting back to affine coordinates needs an inversion.
+pub trait ProjectiveCalculations<T: Field>: EcCalculations<T> {
+    type Point: Clone + std::fmt::Debug + ConditionalSwap;
+
+    fn to_projective(&self, point: &EcPoint<T>) -> Self::Point;
+    fn to_affine(&self, point: &Self::Point) -> EcPoint<T>;
+    fn add_projective(&self, p1: &Self::Point, p2: &Self::Point) -> Self::Point;
+    /// Adds an affine point, which is cheaper than `add_projective`.
+    fn add_mixed(&self, p1: &Self::Point, p2: (&T, &T)) -> Self::Point;
+    fn double_projective(&self, p: &Self::Point) -> Self::Point;
+}
+
+/// Checks that `p1` and `p2`, created separately over equal moduli, agree on the modulus
+/// only by value, and that elements computed from `p1` share its modulus.
+#[cfg(test)]
+pub(crate) fn check_shared_modulus<F, M>(p1: F, p2: F, modulo: impl Fn(&F) -> &std::sync::Arc<M>)
+where
+    F: Field + Inverse,
+    M: PartialEq + std::fmt::Debug,
+{
+    use std::sync::Arc;
+
+    assert!(!Arc::ptr_eq(modulo(&p1), modulo(&p2)));
+    assert_eq!(modulo(&p1), modulo(&p2));
+    let product = p1.clone() * p2;
+    assert!(Arc::ptr_eq(modulo(&product), modulo(&p1)));
+    let derived = (-product.inv()).pow(3);
+    assert!(Arc::ptr_eq(modulo(&derived), modulo(&p1)));
+    assert!(Arc::ptr_eq(modulo(&p1.zero()), modulo(&p1)));
+    assert!(Arc::ptr_eq(modulo(&p1.one()), modulo(&p1)));
 }
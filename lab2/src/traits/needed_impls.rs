@@ -51,3 +51,33 @@ pub fn gcd<
     }
     (b, x, y)
 }
+
+/// Main loop of the Tonelli–Shanks algorithm in a field of order q, where q - 1 = 2^s * t with t odd.
+/// Takes x = a^((t + 1) / 2), b = a^t and c = z^t for some quadratic non-residue z.
+/// Returns a square root of a, or `None` if a is not a square.
+pub fn tonelli_shanks<F: Field>(mut x: F, mut b: F, mut c: F, s: u32) -> Option<F> {
+    let one = x.one();
+    let mut m = s;
+    while b != one {
+        // Least i such that b^(2^i) = 1
+        let mut i = 0;
+        let mut b_pow = b.clone();
+        while b_pow != one {
+            b_pow = b_pow.clone() * b_pow;
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        let mut w = c;
+        for _ in 0..m - i - 1 {
+            w = w.clone() * w;
+        }
+        x = x * w.clone();
+        c = w.clone() * w;
+        b = b * c.clone();
+        m = i;
+    }
+    Some(x)
+}
@@ -0,0 +1,36 @@
+use std::time::Instant;
+
+use lab2::{T, curves, curves::NamedCurve, fp::Fp};
+use rand::Rng;
+
+/// Recounts the points of published curves with Schoof's algorithm
+/// and generates a prime-order curve over F_(2^61 - 1) with a random b.
+fn main() {
+    let start = Instant::now();
+    let secp112r1 = curves::secp112r1();
+    report(secp112r1.name, secp112r1.is_order_correct(), start);
+
+    let start = Instant::now();
+    let secp128r1 = curves::secp128r1();
+    report(secp128r1.name, secp128r1.is_order_correct(), start);
+
+    const P: T = (1 << 61) - 1;
+    let start = Instant::now();
+    let b_from = rand::rng().random_range(0..P);
+    let curve = NamedCurve::generate("generated", Fp::<P>::new(P - 3), b_from);
+    assert!(curve.self_check());
+    println!(
+        "y^2 = x^3 - 3x + {} over F_(2^61 - 1) has prime order {}, found in {:.02?}",
+        curve.ec.b,
+        curve.n,
+        start.elapsed()
+    );
+}
+
+fn report(name: &str, correct: bool, start: Instant) {
+    let verdict = if correct { "correct" } else { "WRONG" };
+    println!(
+        "{name}: order {verdict}, checked in {:.02?}",
+        start.elapsed()
+    );
+}
@@ -0,0 +1,66 @@
+use std::time::Instant;
+
+use lab2::{T, curves::NamedCurve, fp::Fp, scalar::ScalarMul};
+use lab3::discrete_log::{Work, kangaroo, pohlig_hellman, pollard_rho};
+use rand::Rng;
+
+/// Shows how the work of the discrete-log solvers grows with the group: Pollard's rho on
+/// prime-order curves of growing size, the kangaroo on intervals of growing width and
+/// Pohlig–Hellman in the smooth multiplicative group of F_(2^31 - 1).
+fn main() {
+    rho_on_curve(NamedCurve::generate("F_1000003", Fp::<1000003>::new(3), 1));
+    rho_on_curve(NamedCurve::generate(
+        "F_(2^31 - 1)",
+        Fp::<2147483647>::new(3),
+        1,
+    ));
+    rho_on_curve(NamedCurve::generate(
+        "F_(2^32 - 5)",
+        Fp::<4294967291>::new(3),
+        1,
+    ));
+
+    let g = Fp::<2147483647>::new(7);
+    for bits in [16, 24, 30] {
+        let lo = 1 << 30;
+        let x = lo + rand::rng().random_range(0..1 << bits);
+        let start = Instant::now();
+        let (found, work) = kangaroo(&g, &g.mul_vartime(&x), lo, lo + (1 << bits));
+        report(
+            &format!("kangaroo, width 2^{bits}"),
+            found == Some(x),
+            work,
+            start,
+        );
+    }
+
+    let x = rand::rng().random_range(0..2147483646);
+    let start = Instant::now();
+    let (found, work) = pohlig_hellman(&g, &g.mul_vartime(&x), 2147483646);
+    report(
+        "Pohlig–Hellman, n = 2^31 - 2",
+        found == Some(x),
+        work,
+        start,
+    );
+}
+
+fn rho_on_curve<const P: T>(curve: NamedCurve<Fp<P>>) {
+    let n = T::try_from(&curve.n).unwrap();
+    let x = rand::rng().random_range(0..n);
+    let start = Instant::now();
+    let (found, work) = pollard_rho(&curve.g, &curve.g.mul_vartime(&x), n);
+    let name = format!("rho over {}, n ~ 2^{}", curve.name, n.ilog2());
+    report(&name, found == Some(x), work, start);
+}
+
+fn report(name: &str, solved: bool, work: Work, start: Instant) {
+    let verdict = if solved { "solved" } else { "FAILED" };
+    println!(
+        "{name}: {verdict} in {:.02?}, {} operations, {} scalar multiplications, {} stored",
+        start.elapsed(),
+        work.operations,
+        work.scalar_muls,
+        work.stored
+    );
+}
@@ -0,0 +1,64 @@
+use std::time::Instant;
+
+use lab2::{T, arithmetic::pow_mod, fp::Fp};
+use lab3::{
+    diffie_hellman::FpParams,
+    discrete_log::{IndexCalculus, Work, pollard_rho},
+};
+use rand::Rng;
+
+/// Breaks Diffie–Hellman in F_p for safe primes p = 2q + 1 of growing size with index
+/// calculus, next to Pollard's rho in the same subgroups while it stays fast. The index
+/// calculus work grows far slower than sqrt(q), so F_p needs much larger parameters than
+/// elliptic curves of the same security.
+fn main() {
+    attack::<4294967087>(true);
+    attack::<1099511627339>(true);
+    attack::<281474976705359>(false);
+    attack::<72057594037925687>(false);
+    attack::<9223372036854771239>(false);
+    attack::<18446744073709550147>(false);
+}
+
+fn attack<const P: T>(with_rho: bool) {
+    let params = FpParams::<P> {
+        p: P,
+        g: Fp::new(4),
+        q: (P - 1) / 2,
+    };
+    let x = rand::rng().random_range(1..params.q);
+    let h = Fp::<P>::new(pow_mod(4, x, P));
+    let bits = P.ilog2() + 1;
+
+    let start = Instant::now();
+    let solver = IndexCalculus::for_prime(P);
+    let (found, work) = solver.solve(&params, &h);
+    report(
+        &format!("index calculus, {bits}-bit p"),
+        found == Some(x),
+        work,
+        start,
+    );
+
+    if with_rho {
+        let start = Instant::now();
+        let (found, work) = pollard_rho(&params.g, &h, params.q);
+        report(
+            &format!("Pollard's rho, {bits}-bit p"),
+            found == Some(x),
+            work,
+            start,
+        );
+    }
+}
+
+fn report(name: &str, solved: bool, work: Work, start: Instant) {
+    let verdict = if solved { "solved" } else { "FAILED" };
+    println!(
+        "{name}: {verdict} in {:.02?}, {} operations, {} scalar multiplications, {} stored",
+        start.elapsed(),
+        work.operations,
+        work.scalar_muls,
+        work.stored
+    );
+}
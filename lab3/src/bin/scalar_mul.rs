@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+use lab2::{
+    T, curves,
+    f2m::{F2m, bit::Bits8},
+    fp::Fp,
+    scalar::{FixedBase, ScalarMul, multi_mul},
+    traits::Pow,
+};
+use rand::Rng;
+
+const ROUNDS: usize = 200;
+
+/// Compares `Pow::pow` with the ladder, wNAF and a fixed-base comb on random 128-bit scalars.
+fn main() {
+    let mut rng = rand::rng();
+    let scalars: Vec<T> = (0..ROUNDS).map(|_| rng.random()).collect();
+
+    const P: T = (1 << 127) - 1;
+    let g: Fp<P> = Fp::new(rng.random());
+    compare("Fp<2^127 - 1>", &g, &scalars);
+
+    // x^163 + x^7 + x^6 + x^3 + 1
+    let mut modulo = vec![Bits8(0); 21];
+    modulo[0] = Bits8(0b11001001);
+    modulo[20] = Bits8(0b1000);
+    let poly: Vec<Bits8> = (0..20).map(|_| Bits8(rng.random())).collect();
+    let g = F2m::<163>::new_from_slice(&poly, &modulo);
+    compare("F2m<163>", &g, &scalars);
+
+    let g = curves::secp256k1().g;
+    compare("secp256k1", &g, &scalars);
+
+    let bases: Vec<_> = scalars.iter().map(|k| g.mul_vartime(k)).collect();
+    for n in [2, 8, 200] {
+        compare_multi(&format!("secp256k1, {n} terms"), &bases[..n], &scalars[..n]);
+    }
+}
+
+fn compare<G: Pow + ScalarMul + PartialEq + std::fmt::Debug>(name: &str, g: &G, scalars: &[T]) {
+    let table = FixedBase::new(g.clone(), T::BITS);
+    for &k in scalars.iter().take(3) {
+        let expected = g.clone().pow(k);
+        assert_eq!(g.ladder(&k), expected);
+        assert_eq!(g.mul_vartime(&k), expected);
+        assert_eq!(table.mul(&k), expected);
+    }
+
+    println!("{name}");
+    report("pow", scalars, |k| g.clone().pow(k));
+    report("ladder", scalars, |k| g.ladder(&k));
+    report("wnaf", scalars, |k| g.mul_vartime(&k));
+    report("comb", scalars, |k| table.mul(&k));
+}
+
+/// Sum of `scalars[i] * bases[i]` by separate `Pow::pow` calls against `multi_mul`.
+fn compare_multi<G: Pow + ScalarMul + PartialEq + std::fmt::Debug>(
+    name: &str,
+    bases: &[G],
+    scalars: &[T],
+) {
+    let separate = || {
+        bases
+            .iter()
+            .zip(scalars)
+            .map(|(g, &k)| g.clone().pow(k))
+            .reduce(|acc, x| acc * x)
+    };
+    assert_eq!(multi_mul(bases, scalars), separate());
+
+    println!("{name}");
+    report("pow", &[0; 20], |_| separate());
+    report("msm", &[0; 20], |_| multi_mul(bases, scalars));
+}
+
+fn report<G>(label: &str, scalars: &[T], f: impl Fn(T) -> G) {
+    let start = Instant::now();
+    for &k in scalars {
+        std::hint::black_box(f(k));
+    }
+    let per_call: Duration = (Instant::now() - start) / scalars.len() as u32;
+    println!("  {label:<7} {:>10.02?}", per_call);
+}
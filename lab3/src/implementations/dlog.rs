@@ -0,0 +1,489 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Add,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+};
+
+use lab2::{
+    T,
+    arithmetic::{factor, gcd, inverse_mod, mul_mod, sub_mod},
+    scalar::ScalarMul,
+};
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::diffie_hellman::ParamsForDiffieHellman;
+
+/// Group the solvers work in, written additively as in `ScalarMul`.
+/// Tables are keyed by the elements, and their hashes also steer the random walks.
+pub trait DlogGroup: ScalarMul + Hash + Eq + Send + Sync {}
+
+impl<G: ScalarMul + Hash + Eq + Send + Sync> DlogGroup for G {}
+
+/// Work done by a solver.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Work {
+    /// Single group operations of tables and walks: multiplications in fields,
+    /// additions on curves.
+    pub operations: u64,
+    /// Full scalar multiplications, each worth a few times log2(n) operations.
+    pub scalar_muls: u64,
+    /// Elements held in memory, such as baby steps or distinguished points.
+    pub stored: u64,
+}
+
+impl Add for Work {
+    type Output = Work;
+
+    fn add(self, other: Work) -> Work {
+        Work {
+            operations: self.operations + other.operations,
+            scalar_muls: self.scalar_muls + other.scalar_muls,
+            stored: self.stored + other.stored,
+        }
+    }
+}
+
+/// Baby-step giant-step: writes x = i * m + j with m about sqrt(n) and looks the giant
+/// steps h - i * m * g up in a table of the baby steps j * g.
+/// Both the table and the giant steps are split between threads.
+/// Finds x in [0, n) with x * g = h, where n is a multiple of the order of g.
+pub fn baby_step_giant_step<G: DlogGroup>(g: &G, h: &G, n: T) -> (Option<T>, Work) {
+    let m = n.max(1).isqrt() + 1;
+    let threads = rayon::current_num_threads() as T;
+    let baby_steps: HashMap<G, T> = chunks(m, threads)
+        .into_par_iter()
+        .flat_map_iter(|(from, to)| {
+            let mut point = g.mul_vartime(&from);
+            (from..to).map(move |j| {
+                let key = point.clone();
+                point = add(&point, g);
+                (key, j)
+            })
+        })
+        .collect();
+
+    let operations = AtomicU64::new(m as u64);
+    let giant_step = g.mul_vartime(&m).negate();
+    let found = chunks(n.div_ceil(m), threads)
+        .into_par_iter()
+        .find_map_any(|(from, to)| {
+            let mut point = add(h, &giant_step.mul_vartime(&from));
+            for i in from..to {
+                if let Some(j) = baby_steps.get(&point) {
+                    return Some((i * m + j) % n);
+                }
+                point = add(&point, &giant_step);
+                operations.fetch_add(1, Ordering::Relaxed);
+            }
+            None
+        });
+
+    let work = Work {
+        operations: operations.into_inner(),
+        scalar_muls: 2 * threads as u64 + 1,
+        stored: baby_steps.len() as u64,
+    };
+    (found, work)
+}
+
+/// Pollard's rho with an r-adding walk. Every thread runs its own walk and stores the
+/// distinguished points it meets in a shared table; two walks through the same point
+/// give a x + b x * g relation. Expects about sqrt(pi n / 2) steps in total.
+/// n should be the prime order of g, for composite orders use `pohlig_hellman`.
+pub fn pollard_rho<G: DlogGroup>(g: &G, h: &G, n: T) -> (Option<T>, Work) {
+    const STEPS: usize = 20;
+    let mut rng = rand::rng();
+    let steps: Vec<(T, T, G)> = (0..STEPS)
+        .map(|_| {
+            let (a, b) = (rng.random_range(0..n), rng.random_range(0..n));
+            (a, b, add(&g.mul_vartime(&a), &h.mul_vartime(&b)))
+        })
+        .collect();
+
+    let distinguished_bits = n.ilog2() / 4;
+    let budget = 16 * n.isqrt() as u64 + 1000;
+    let threads = rayon::current_num_threads();
+    let table = Mutex::new(HashMap::new());
+    let (done, operations) = (AtomicBool::new(false), AtomicU64::new(0));
+    let scalar_muls = AtomicU64::new(2 * STEPS as u64);
+
+    let found = (0..threads).into_par_iter().find_map_any(|_| {
+        let mut rng = rand::rng();
+        'restart: while !done.load(Ordering::Relaxed) {
+            let (mut a, mut b) = (rng.random_range(0..n), rng.random_range(0..n));
+            let mut point = add(&g.mul_vartime(&a), &h.mul_vartime(&b));
+            scalar_muls.fetch_add(2, Ordering::Relaxed);
+            // A walk stuck in a cycle without distinguished points starts over
+            for _ in 0..20 << distinguished_bits {
+                if operations.fetch_add(1, Ordering::Relaxed) > budget {
+                    return None;
+                }
+                let hash = hash_of(&point);
+                if is_distinguished(hash, distinguished_bits) {
+                    let previous = table.lock().unwrap().insert(point.clone(), (a, b));
+                    match previous {
+                        Some((a2, b2)) if (a2, b2) != (a, b) => {
+                            // a + b x = a2 + b2 x (mod n)
+                            scalar_muls.fetch_add(1, Ordering::Relaxed);
+                            let x = solve_linear(sub_mod(b, b2, n), sub_mod(a2, a, n), n)
+                                .into_iter()
+                                .find(|x| g.mul_vartime(x) == *h);
+                            if x.is_some() {
+                                done.store(true, Ordering::Relaxed);
+                                return x;
+                            }
+                            continue 'restart;
+                        }
+                        Some(_) => continue 'restart,
+                        None => {}
+                    }
+                }
+
+                let (a_i, b_i, step) = &steps[hash as usize % STEPS];
+                point = add(&point, step);
+                (a, b) = ((a + a_i) % n, (b + b_i) % n);
+            }
+        }
+        None
+    });
+
+    let work = Work {
+        operations: operations.into_inner(),
+        scalar_muls: scalar_muls.into_inner(),
+        stored: table.into_inner().unwrap().len() as u64,
+    };
+    (found, work)
+}
+
+/// Pollard's kangaroo for x in [lo, hi]. Every thread runs a tame kangaroo from a known
+/// multiple of g in the upper half of the interval and a wild one from h, taking steps in
+/// turn; all jump by powers of two chosen by the hash of the point. A tame and a wild
+/// kangaroo landing on the same distinguished point give x. About 2 sqrt(hi - lo) steps.
+pub fn kangaroo<G: DlogGroup>(g: &G, h: &G, lo: T, hi: T) -> (Option<T>, Work) {
+    let width = hi - lo;
+    let threads = rayon::current_num_threads() as T;
+    // The mean jump is about threads * sqrt(width) / 2
+    let mean = (threads * width.isqrt() / 2).max(1);
+    let jump_count = (1..T::BITS).find(|&r| (1 << r) / r as T >= mean).unwrap();
+    let mut jumps = vec![g.clone()];
+    for _ in 1..jump_count {
+        jumps.push(add(jumps.last().unwrap(), jumps.last().unwrap()));
+    }
+
+    let distinguished_bits = (mean.ilog2() / 2).min(16);
+    let budget = 64 * width.isqrt() as u64 + 1000;
+    let table = Mutex::new(HashMap::new());
+    let (done, operations) = (AtomicBool::new(false), AtomicU64::new(jump_count as u64));
+    let scalar_muls = AtomicU64::new(0);
+
+    let found = (0..threads).into_par_iter().find_map_any(|_| {
+        let mut rng = rand::rng();
+        // Distance walked from g * lo for tame kangaroos and from h for wild ones
+        let mut start = |tame: bool| {
+            scalar_muls.fetch_add(1, Ordering::Relaxed);
+            let spread = rng.random_range(0..=mean);
+            match tame {
+                true => (
+                    width / 2 + spread,
+                    g.mul_vartime(&(lo + width / 2 + spread)),
+                    0,
+                ),
+                false => (spread, add(h, &g.mul_vartime(&spread)), 0),
+            }
+        };
+        let mut herd = [(true, start(true)), (false, start(false))];
+
+        while !done.load(Ordering::Relaxed) {
+            for (tame, (distance, point, steps)) in herd.iter_mut() {
+                if operations.fetch_add(1, Ordering::Relaxed) > budget {
+                    return None;
+                }
+                let hash = hash_of(point);
+                // A kangaroo without distinguished points for long is likely stuck
+                let mut restart = *steps > 20 << distinguished_bits;
+                if is_distinguished(hash, distinguished_bits) {
+                    let previous = table
+                        .lock()
+                        .unwrap()
+                        .insert(point.clone(), (*distance, *tame));
+                    match previous {
+                        Some((other, other_tame)) if other_tame != *tame => {
+                            let (tame_distance, wild_distance) = match tame {
+                                true => (*distance, other),
+                                false => (other, *distance),
+                            };
+                            scalar_muls.fetch_add(1, Ordering::Relaxed);
+                            let x = (lo + tame_distance)
+                                .checked_sub(wild_distance)
+                                .filter(|x| (lo..=hi).contains(x) && g.mul_vartime(x) == *h);
+                            if x.is_some() {
+                                done.store(true, Ordering::Relaxed);
+                                return x;
+                            }
+                            restart = true;
+                        }
+                        // Following another kangaroo of the same kind is wasted work
+                        Some(_) => restart = true,
+                        None => *steps = 0,
+                    }
+                }
+
+                if restart {
+                    (*distance, *point, *steps) = start(*tame);
+                    continue;
+                }
+                let i = hash as usize % jumps.len();
+                *point = add(point, &jumps[i]);
+                *distance += 1 << i;
+                *steps += 1;
+            }
+        }
+        None
+    });
+
+    let work = Work {
+        operations: operations.into_inner(),
+        scalar_muls: scalar_muls.into_inner(),
+        stored: table.into_inner().unwrap().len() as u64,
+    };
+    (found, work)
+}
+
+/// Pohlig–Hellman: finds x modulo each prime power p^e of n, one base-p digit at a time
+/// in the subgroup of order p, and puts the results together with the CRT.
+/// The prime powers are solved in parallel. The work grows with the square root of the
+/// largest prime factor of n, which is why group orders need a large prime factor.
+/// n must be a multiple of the order of g below 2^127, the result is x modulo the order.
+pub fn pohlig_hellman<G: DlogGroup>(g: &G, h: &G, n: T) -> (Option<T>, Work) {
+    let parts: Vec<(Option<(T, T)>, Work)> = factor(n)
+        .into_par_iter()
+        .map(|(p, e)| {
+            let (g_e, h_e) = (
+                g.mul_vartime(&(n / p.pow(e))),
+                h.mul_vartime(&(n / p.pow(e))),
+            );
+            let mut work = Work {
+                scalar_muls: 2,
+                ..Work::default()
+            };
+            // The order of g_e is p^e only when p^e divides the order of g
+            let mut e = e;
+            while e > 0 && g_e.mul_vartime(&p.pow(e - 1)).is_identity() {
+                work.scalar_muls += 1;
+                e -= 1;
+            }
+            let p_e = p.pow(e);
+            let generator = g_e.mul_vartime(&(p_e / p.min(p_e)));
+
+            // x = d_0 + d_1 p + ... + d_(e-1) p^(e-1)
+            let (mut x, mut p_k) = (0, 1);
+            for k in 0..e {
+                let rest = add(&h_e, &g_e.mul_vartime(&x).negate());
+                let target = rest.mul_vartime(&(p_e / p_k / p));
+                let (digit, digit_work) = match p < 1 << 32 {
+                    true => baby_step_giant_step(&generator, &target, p),
+                    false => pollard_rho(&generator, &target, p),
+                };
+                work = work + digit_work;
+                work.scalar_muls += 2;
+                let Some(digit) = digit else {
+                    return (None, work);
+                };
+                x += digit * p_k;
+                if k + 1 < e {
+                    p_k *= p;
+                }
+            }
+            (Some((x, p_e)), work)
+        })
+        .collect();
+
+    let work = parts.iter().fold(Work::default(), |acc, (_, w)| acc + *w);
+    let Some(residues) = parts
+        .into_iter()
+        .map(|(r, _)| r)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return (None, work);
+    };
+    let (x, _) = residues.into_iter().fold((0, 1), |(x, modulus), (r, m)| {
+        // x + k * modulus = r (mod m)
+        let k = mul_mod(sub_mod(r, x, m), inverse_mod(modulus % m, m).unwrap(), m);
+        (x + k * modulus, modulus * m)
+    });
+    let x = Some(x).filter(|x| g.mul_vartime(x) == *h);
+    (
+        x,
+        work + Work {
+            scalar_muls: 1,
+            ..Work::default()
+        },
+    )
+}
+
+/// Recovers a secret key from a public key by `pohlig_hellman`, with q as the order of g.
+/// Any solution gives the same public key and shared secrets as the real key.
+pub fn recover_secret_key<P>(params: &P, public_key: &P::G) -> (Option<T>, Work)
+where
+    P: ParamsForDiffieHellman,
+    P::G: DlogGroup,
+{
+    pohlig_hellman(&params.get_g(), public_key, params.get_q())
+}
+
+/// Group operation on elements, the identity does not need to know its group.
+fn add<G: ScalarMul>(a: &G, b: &G) -> G {
+    match (a.is_identity(), b.is_identity()) {
+        (true, _) => b.clone(),
+        (_, true) => a.clone(),
+        _ => a.normalize(&a.add_repr(&a.to_repr(), &b.to_repr())),
+    }
+}
+
+/// Splits [0, n) into `parts` ranges of about equal length.
+fn chunks(n: T, parts: T) -> Vec<(T, T)> {
+    let size = n.div_ceil(parts).max(1);
+    (0..n.div_ceil(size))
+        .map(|i| (i * size, ((i + 1) * size).min(n)))
+        .collect()
+}
+
+/// The same for equal elements in every thread, as `DefaultHasher::new` has fixed keys.
+fn hash_of<G: Hash>(element: &G) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    element.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Uses the high bits, the low ones pick the steps of the walks.
+fn is_distinguished(hash: u64, bits: u32) -> bool {
+    hash >> (64 - bits.max(1)) == 0 || bits == 0
+}
+
+/// All x in [0, n) with a x = b (mod n), at most a few thousand of them.
+fn solve_linear(a: T, b: T, n: T) -> Vec<T> {
+    let d = gcd(a, n);
+    if !b.is_multiple_of(d) || d > 1 << 12 {
+        return vec![];
+    }
+    let m = n / d;
+    let x = mul_mod(b / d, inverse_mod(a / d, m).unwrap(), m);
+    (0..d).map(|k| x + k * m).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diffie_hellman::{DiffieHellman, FpParams};
+    use lab2::{
+        curves::{self, NamedCurve},
+        elliptic_curve::EcPoint,
+        f2m::{F2m, bit::Bits8},
+        fp::Fp,
+        fpk::Fpk,
+        polynomials::Polynomial,
+        traits::EcCalculations,
+    };
+
+    /// Curve over F_1000003 with a prime number of points.
+    fn prime_order_curve() -> (EcPoint<Fp<1000003>>, T) {
+        let curve = NamedCurve::generate("test", Fp::<1000003>::new(3), 1);
+        (curve.g, T::try_from(&curve.n).unwrap())
+    }
+
+    #[test]
+    fn test_baby_step_giant_step() {
+        let g = Fp::<65537>::new(3);
+        for x in [0, 1, 12345, 65535] {
+            let h = g.mul_vartime(&x);
+            let (found, work) = baby_step_giant_step(&g, &h, 65536);
+            assert_eq!(found, Some(x));
+            assert!(work.operations <= 2 * 257 + 1);
+        }
+
+        let curve = curves::small_f23();
+        let h = curve.g.mul_vartime(&5_u8);
+        assert_eq!(baby_step_giant_step(&curve.g, &h, 7).0, Some(5));
+        assert_eq!(
+            baby_step_giant_step(&curve.g, &curve.ec.get_infinity(), 7).0,
+            Some(0)
+        );
+
+        // x^7 + x + 1 generates the 127 - 1 non-zero elements of GF(2^7)
+        let modulo = [Bits8(0b10000011)];
+        let g = F2m::<7>::new_from_slice(&[Bits8(0b10)], &modulo);
+        let h = g.mul_vartime(&100_u8);
+        assert_eq!(baby_step_giant_step(&g, &h, 127).0, Some(100));
+    }
+
+    #[test]
+    fn test_pollard_rho() {
+        let (g, n) = prime_order_curve();
+        let x = n / 3 + 17;
+        let (found, work) = pollard_rho(&g, &g.mul_vartime(&x), n);
+        assert_eq!(found, Some(x));
+        assert!(work.operations < 16 * n.isqrt() as u64 + 1000);
+
+        let curve = curves::small_f17();
+        let h = curve.g.mul_vartime(&11_u8);
+        assert_eq!(pollard_rho(&curve.g, &h, 19).0, Some(11));
+    }
+
+    #[test]
+    fn test_kangaroo() {
+        let (g, n) = prime_order_curve();
+        let (lo, hi) = (n / 2, n / 2 + 40000);
+        for x in [lo, lo + 12345, hi] {
+            let (found, work) = kangaroo(&g, &g.mul_vartime(&x), lo, hi);
+            assert_eq!(found, Some(x));
+            assert!(work.operations < 64 * 200 + 1000);
+        }
+
+        let g = Fp::<65537>::new(3);
+        assert_eq!(
+            kangaroo(&g, &g.mul_vartime(&777_u16), 700, 800).0,
+            Some(777)
+        );
+    }
+
+    #[test]
+    fn test_pohlig_hellman() {
+        let g = Fp::<65537>::new(3);
+        let h = g.mul_vartime(&54321_u16);
+        let (found, work) = pohlig_hellman(&g, &h, 65536);
+        assert_eq!(found, Some(54321));
+        assert!(work.operations < 16 * 4);
+
+        // 47^2 - 1 = 2^5 * 3 * 23 is only a multiple of the order of g
+        let modulo: Polynomial<Fp<47>> = Polynomial::new_from_slice(&[1, 0, 1]);
+        let g: Fpk<47, 2> = Fpk::new(Polynomial::new_from_slice(&[2, 1]), modulo);
+        let h = g.mul_vartime(&1000_u16);
+        let found = pohlig_hellman(&g, &h, 2208).0.unwrap();
+        assert_eq!(g.mul_vartime(&found), h);
+
+        let curve = curves::small_f23();
+        let ec_g = curve.ec.get_point_on_curve(Fp::new(1), false).unwrap();
+        let h = ec_g.mul_vartime(&9_u8);
+        let found = pohlig_hellman(&ec_g, &h, 28).0.unwrap();
+        assert_eq!(ec_g.mul_vartime(&found), h);
+    }
+
+    #[test]
+    fn test_recover_secret_key() {
+        let params: FpParams<65537> = FpParams {
+            p: 65537,
+            g: Fp::<65537>::new(15302),
+            q: 65536,
+        };
+        let secret = Fp::<65537>::generate_secret_key(&params, 31337);
+        let public = Fp::<65537>::compute_public_key(&params, &secret);
+        let (recovered, _) = recover_secret_key(&params, &public);
+        let recovered = recovered.unwrap();
+        assert_eq!(Fp::<65537>::compute_public_key(&params, &recovered), public);
+    }
+}
@@ -0,0 +1,483 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use lab2::{
+    T,
+    arithmetic::{factor, gcd, inverse_mod, mul_mod, pow_mod, sub_mod},
+    fp::Fp,
+};
+use rand::Rng;
+use rayon::prelude::*;
+
+use super::{
+    dlog::{Work, pohlig_hellman},
+    zad3::FpParams,
+};
+
+/// Prime-power parts of p - 1 up to this size are left to Pohlig–Hellman.
+const SMALL_PART: T = 1 << 24;
+/// Candidates each thread tests in a round of relation collection.
+const BATCH: T = 1 << 12;
+/// Candidates tried for an individual logarithm before giving up.
+const DESCENT_ATTEMPTS: T = 1 << 20;
+
+/// Index-calculus solver for discrete logarithms in F_p* with p below 2^64.
+///
+/// Relations γ^k = ±a / b (mod p) are collected for a generator γ, where a and b of
+/// about sqrt(p) come from rational reconstruction and both factor over the factor base.
+/// A sparse elimination modulo every large prime power of p - 1 gives the logarithms of
+/// the factor base, and the logarithm of any element then follows from a single smooth
+/// y * γ^k. The work grows like exp(c sqrt(ln p ln ln p)) against sqrt(q) for the
+/// generic solvers, which is why finite-field groups need thousands of bits where
+/// elliptic curves get by with a few hundred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexCalculus {
+    /// The factor base is -1 and the primes up to this bound.
+    pub factor_base_bound: T,
+    /// Relations collected beyond the size of the factor base.
+    pub extra_relations: usize,
+}
+
+/// γ^k = product of the factor base elements to the given exponents, index 0 is -1.
+struct Relation {
+    k: T,
+    factors: Vec<(usize, i64)>,
+}
+
+/// Sparse row of a linear system, sorted by column, with its right-hand side.
+type Row = (Vec<(usize, T)>, T);
+
+impl IndexCalculus {
+    /// Bound around exp(sqrt(ln s ln ln s)) for the halves s = sqrt(p) of the relations.
+    pub fn for_prime(p: T) -> Self {
+        let log_s = (p as f64).ln() / 2.0;
+        let bound = (log_s * log_s.ln()).sqrt().exp();
+        IndexCalculus {
+            factor_base_bound: (bound as T).max(64),
+            extra_relations: 32,
+        }
+    }
+
+    /// x with g^x = h for the parameters of Diffie–Hellman in F_P, modulo the order of g.
+    /// q must be a multiple of the order of g, only the primes of q are worked on.
+    pub fn solve<const P: T>(&self, params: &FpParams<P>, h: &Fp<P>) -> (Option<T>, Work) {
+        assert!(P < 1 << 64, "Index calculus is meant for primes below 2^64");
+        let (p, n) = (P, P - 1);
+        let mut work = Work::default();
+        let factors = factor(n);
+        let gamma = (2..p)
+            .find(|&a| factors.iter().all(|(l, _)| pow_mod(a, n / l, p) != 1))
+            .unwrap();
+
+        let parts: Vec<(T, T)> = factors
+            .iter()
+            .filter(|(l, _)| params.q.is_multiple_of(*l))
+            .map(|&(l, e)| (l, l.pow(e)))
+            .collect();
+        let (small, large): (Vec<_>, Vec<_>) = parts.iter().partition(|(_, m)| *m <= SMALL_PART);
+
+        // log_γ g and log_γ h modulo every part
+        let mut residues = Vec::new();
+        for &(_, m) in &small {
+            let project = |y: T| Fp::<P>::new(pow_mod(y, n / m, p));
+            let (log_g, g_work) = pohlig_hellman(&project(gamma), &project(params.g.get()), m);
+            let (log_h, h_work) = pohlig_hellman(&project(gamma), &project(h.get()), m);
+            work = work + g_work + h_work;
+            work.scalar_muls += 3;
+            let (Some(log_g), Some(log_h)) = (log_g, log_h) else {
+                return (None, work);
+            };
+            residues.push((log_g, log_h, m));
+        }
+
+        if !large.is_empty() {
+            let factor_base = self.factor_base();
+            let columns = factor_base.len() + 1;
+            let relations = self.collect_relations(p, gamma, &factor_base, &mut work);
+            let tables: Vec<(T, Vec<Option<T>>)> = large
+                .par_iter()
+                .map(|&(l, m)| {
+                    let logs = solve_system(&relations, columns, l, m);
+                    (m, verified(logs, &factor_base, p, gamma, m))
+                })
+                .collect();
+            work.scalar_muls += (large.len() * columns) as u64;
+
+            let log_g = individual_log(params.g.get(), p, gamma, &factor_base, &tables, &mut work);
+            let log_h = individual_log(h.get(), p, gamma, &factor_base, &tables, &mut work);
+            let (Some(log_g), Some(log_h)) = (log_g, log_h) else {
+                return (None, work);
+            };
+            for ((log_g, log_h), (m, _)) in log_g.into_iter().zip(log_h).zip(&tables) {
+                residues.push((log_g, log_h, *m));
+            }
+        }
+
+        // log g * x = log h modulo the product of the parts
+        let (log_g, log_h, modulus) =
+            residues
+                .into_iter()
+                .fold((0, 0, 1), |(log_g, log_h, modulus), (g_r, h_r, m)| {
+                    let lift = |x: T, r: T| {
+                        let k = mul_mod(sub_mod(r, x, m), inverse_mod(modulus % m, m).unwrap(), m);
+                        x + k * modulus
+                    };
+                    (lift(log_g, g_r), lift(log_h, h_r), modulus * m)
+                });
+        let d = gcd(log_g, modulus);
+        if !log_h.is_multiple_of(d) {
+            return (None, work);
+        }
+        let order = modulus / d;
+        let x = mul_mod(log_h / d, inverse_mod(log_g / d, order).unwrap(), order);
+        work.scalar_muls += 1;
+        let x = Some(x).filter(|&x| pow_mod(params.g.get(), x, p) == h.get());
+        (x, work)
+    }
+
+    /// Primes up to the bound, by the sieve of Eratosthenes.
+    fn factor_base(&self) -> Vec<u64> {
+        let bound = self.factor_base_bound as usize;
+        let mut composite = vec![false; bound + 1];
+        (2..=bound)
+            .filter(|&i| {
+                if !composite[i] {
+                    (i * i..=bound).step_by(i).for_each(|j| composite[j] = true);
+                }
+                !composite[i]
+            })
+            .map(|i| i as u64)
+            .collect()
+    }
+
+    /// Tests powers of γ on every thread until there are enough smooth ones. Every thread
+    /// walks with a random step, as consecutive powers of a small γ give the same relations.
+    fn collect_relations(
+        &self,
+        p: T,
+        gamma: T,
+        factor_base: &[u64],
+        work: &mut Work,
+    ) -> Vec<Relation> {
+        let needed = factor_base.len() + 1 + self.extra_relations;
+        let threads = rayon::current_num_threads();
+        let mut relations = Vec::new();
+        while relations.len() < needed {
+            let found: Vec<Relation> = (0..threads)
+                .into_par_iter()
+                .flat_map_iter(|_| {
+                    let mut rng = rand::rng();
+                    let (start, step) = (rng.random_range(0..p - 1), rng.random_range(1..p - 1));
+                    let (mut power, multiplier) =
+                        (pow_mod(gamma, start, p), pow_mod(gamma, step, p));
+                    (0..BATCH).filter_map(move |i| {
+                        let relation = decompose(power, p, factor_base).map(|factors| Relation {
+                            k: (start + mul_mod(i, step, p - 1)) % (p - 1),
+                            factors,
+                        });
+                        power = mul_mod(power, multiplier, p);
+                        relation
+                    })
+                })
+                .collect();
+            work.operations += (threads as T * BATCH) as u64;
+            work.scalar_muls += 2 * threads as u64;
+            relations.extend(found);
+        }
+        work.stored += relations.len() as u64;
+        relations
+    }
+}
+
+/// Writes r = ±a / b (mod p) with a and b around sqrt(p) and factors both over the base.
+fn decompose(r: T, p: T, factor_base: &[u64]) -> Option<Vec<(usize, i64)>> {
+    let bound = p.isqrt() as i128;
+    let (mut r0, mut r1) = (p as i128, r as i128);
+    let (mut t0, mut t1) = (0_i128, 1_i128);
+    // r1 = t1 * r (mod p) throughout
+    while r1 > bound {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q * t1);
+    }
+
+    let mut factors = Vec::new();
+    if t1 < 0 {
+        factors.push((0, 1));
+    }
+    smooth_part(r1 as u64, 1, factor_base, &mut factors)?;
+    smooth_part(t1.unsigned_abs() as u64, -1, factor_base, &mut factors)?;
+    factors.sort_unstable_by_key(|&(i, _)| i);
+    factors.dedup_by(|(i, e), (first, sum)| {
+        let same = i == first;
+        if same {
+            *sum += *e;
+        }
+        same
+    });
+    factors.retain(|&(_, e)| e != 0);
+    Some(factors)
+}
+
+/// Pushes the exponents of a over the factor base, `None` if a does not factor over it.
+fn smooth_part(
+    mut a: u64,
+    sign: i64,
+    factor_base: &[u64],
+    factors: &mut Vec<(usize, i64)>,
+) -> Option<()> {
+    for (i, &prime) in factor_base.iter().enumerate() {
+        if prime * prime > a {
+            break;
+        }
+        let mut exponent = 0;
+        while a.is_multiple_of(prime) {
+            a /= prime;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((i + 1, sign * exponent));
+        }
+    }
+    if a > 1 {
+        factors.push((factor_base.binary_search(&a).ok()? + 1, sign));
+    }
+    Some(())
+}
+
+/// Logarithms of the factor base modulo m = l^e, `None` where the relations do not
+/// determine them. Gaussian elimination on sparse rows, taking the columns from the
+/// lightest and the shortest row with a coefficient invertible modulo m as the pivot.
+fn solve_system(relations: &[Relation], columns: usize, l: T, m: T) -> Vec<Option<T>> {
+    let mut rows: Vec<Row> = relations
+        .iter()
+        .map(|relation| {
+            let row = relation
+                .factors
+                .iter()
+                .map(|&(c, e)| (c, signed_mod(e, m)))
+                .filter(|&(_, e)| e != 0)
+                .collect();
+            (row, relation.k % m)
+        })
+        .collect();
+    let mut weights = vec![0; columns];
+    rows.iter()
+        .flat_map(|(row, _)| row)
+        .for_each(|&(c, _)| weights[c] += 1);
+    let mut order: Vec<usize> = (0..columns).filter(|&c| weights[c] > 0).collect();
+    order.sort_by_key(|&c| weights[c]);
+
+    let mut pivots = Vec::new();
+    for column in order {
+        let pivot = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, (row, _))| coefficient(row, column).is_some_and(|c| !c.is_multiple_of(l)))
+            .min_by_key(|(_, (row, _))| row.len())
+            .map(|(i, _)| i);
+        let Some(i) = pivot else {
+            continue;
+        };
+        let (row, rhs) = rows.swap_remove(i);
+        let inverse = inverse_mod(coefficient(&row, column).unwrap(), m).unwrap();
+        let row = combine(&[], &row, inverse, m);
+        let rhs = mul_mod(rhs, inverse, m);
+        for (other, other_rhs) in rows.iter_mut() {
+            if let Some(c) = coefficient(other, column) {
+                *other = combine(other, &row, m - c, m);
+                *other_rhs = sub_mod(*other_rhs, mul_mod(c, rhs, m), m);
+            }
+        }
+        pivots.push((column, row, rhs));
+    }
+
+    // A pivot row only holds columns eliminated after it, or never
+    let mut logs = vec![None; columns];
+    for (column, row, rhs) in pivots.into_iter().rev() {
+        logs[column] = row
+            .iter()
+            .filter(|&&(c, _)| c != column)
+            .try_fold(rhs, |acc, &(c, coefficient)| {
+                Some(sub_mod(acc, mul_mod(coefficient, logs[c]?, m), m))
+            });
+    }
+    logs
+}
+
+fn signed_mod(e: i64, m: T) -> T {
+    match e < 0 {
+        true => sub_mod(0, e.unsigned_abs() as T, m),
+        false => e as T % m,
+    }
+}
+
+fn coefficient(row: &[(usize, T)], column: usize) -> Option<T> {
+    row.binary_search_by_key(&column, |&(c, _)| c)
+        .ok()
+        .map(|i| row[i].1)
+}
+
+/// a + factor * b modulo m, for sorted rows.
+fn combine(a: &[(usize, T)], b: &[(usize, T)], factor: T, m: T) -> Vec<(usize, T)> {
+    let mut result = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let entry = match (a.get(i), b.get(j)) {
+            (Some(&(ca, x)), Some(&(cb, _))) if ca < cb => {
+                i += 1;
+                (ca, x)
+            }
+            (Some(&(ca, x)), Some(&(cb, y))) if ca == cb => {
+                (i, j) = (i + 1, j + 1);
+                (ca, (x + mul_mod(factor, y, m)) % m)
+            }
+            (_, Some(&(cb, y))) => {
+                j += 1;
+                (cb, mul_mod(factor, y, m))
+            }
+            (Some(&entry), None) => {
+                i += 1;
+                entry
+            }
+            (None, None) => unreachable!(),
+        };
+        if entry.1 != 0 {
+            result.push(entry);
+        }
+    }
+    result
+}
+
+/// Drops the logarithms modulo m that do not satisfy γ^(log * (p - 1) / m) = f^((p - 1) / m).
+fn verified(logs: Vec<Option<T>>, factor_base: &[u64], p: T, gamma: T, m: T) -> Vec<Option<T>> {
+    let n = p - 1;
+    let base = pow_mod(gamma, n / m, p);
+    logs.into_iter()
+        .enumerate()
+        .map(|(i, log)| {
+            let element = if i == 0 {
+                p - 1
+            } else {
+                factor_base[i - 1] as T
+            };
+            log.filter(|&log| pow_mod(base, log, p) == pow_mod(element, n / m, p))
+        })
+        .collect()
+}
+
+/// log_γ y modulo every table's m, from some smooth y * γ^k whose factors all have
+/// known logarithms.
+fn individual_log(
+    y: T,
+    p: T,
+    gamma: T,
+    factor_base: &[u64],
+    tables: &[(T, Vec<Option<T>>)],
+    work: &mut Work,
+) -> Option<Vec<T>> {
+    let operations = AtomicU64::new(0);
+    let logs = (0..DESCENT_ATTEMPTS).into_par_iter().find_map_any(|_| {
+        operations.fetch_add(1, Ordering::Relaxed);
+        let k = rand::rng().random_range(0..p - 1);
+        let factors = decompose(mul_mod(y, pow_mod(gamma, k, p), p), p, factor_base)?;
+        tables
+            .iter()
+            .map(|(m, logs)| {
+                factors.iter().try_fold(sub_mod(0, k, *m), |acc, &(i, e)| {
+                    Some((acc + mul_mod(signed_mod(e, *m), logs[i]?, *m)) % m)
+                })
+            })
+            .collect::<Option<Vec<T>>>()
+    });
+    let operations = operations.into_inner();
+    work.operations += operations;
+    work.scalar_muls += operations;
+    logs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diffie_hellman::DiffieHellman;
+
+    /// 2^32 - 209 = 2q + 1 with q prime.
+    const P: T = 4294967087;
+
+    #[test]
+    fn test_index_calculus() {
+        // 4 generates the subgroup of order q, 5 all of F_P*
+        for (g, q) in [(4, (P - 1) / 2), (5, P - 1)] {
+            let params = FpParams::<P> {
+                p: P,
+                g: Fp::new(g),
+                q,
+            };
+            let solver = IndexCalculus::for_prime(P);
+            for x in [1, 2, q / 3, q - 1] {
+                let h = Fp::new(pow_mod(g, x, P));
+                let (found, work) = solver.solve(&params, &h);
+                assert_eq!(found, Some(x));
+                assert!(work.stored > solver.factor_base().len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_index_calculus_64_bits() {
+        // 2^64 - 1469 is a safe prime as well
+        const P: T = 18446744073709550147;
+        let params = FpParams::<P> {
+            p: P,
+            g: Fp::new(4),
+            q: (P - 1) / 2,
+        };
+        let x = 0x123456789abcdef;
+        let h = Fp::new(pow_mod(4, x, P));
+        let (found, _) = IndexCalculus::for_prime(P).solve(&params, &h);
+        assert_eq!(found, Some(x));
+    }
+
+    #[test]
+    fn test_index_calculus_keys() {
+        // 1048343 = 2 * 524171 + 1 is small enough for Pohlig–Hellman alone
+        let params = FpParams::<1048343> {
+            p: 1048343,
+            g: Fp::new(4),
+            q: 524171,
+        };
+        let secret = Fp::<1048343>::generate_secret_key(&params, 777777);
+        let public = Fp::<1048343>::compute_public_key(&params, &secret);
+        let (found, work) = IndexCalculus::for_prime(1048343).solve(&params, &public);
+        assert_eq!(found, Some(secret));
+        assert!(work.operations < 1 << 12);
+    }
+
+    #[test]
+    fn test_solve_system() {
+        // 2 x0 + x1 = 5, x0 + x1 = 3, x1 = 1 modulo 7
+        let relations = [
+            Relation {
+                k: 5,
+                factors: vec![(0, 2), (1, 1)],
+            },
+            Relation {
+                k: 3,
+                factors: vec![(0, 1), (1, 1)],
+            },
+            Relation {
+                k: 1,
+                factors: vec![(1, 1)],
+            },
+        ];
+        assert_eq!(
+            solve_system(&relations, 3, 7, 7),
+            vec![Some(2), Some(1), None]
+        );
+        // Only 2 x0 = 4 modulo 4 leaves x0 open
+        let relations = [Relation {
+            k: 4,
+            factors: vec![(0, 2)],
+        }];
+        assert_eq!(solve_system(&relations, 1, 2, 4), vec![None]);
+    }
+}
@@ -1,3 +1,5 @@
+pub mod dlog;
+pub mod index_calculus;
 pub mod zad1;
 pub mod zad3;
 pub mod zad4;
@@ -9,14 +9,16 @@ use lab2::{
     elliptic_curve::{Ec, EcPoint},
     f2m::F2m,
     fp::Fp,
+    fp_mont::{FpMont, MontgomeryParams},
     fpk::Fpk,
-    traits::{EcCalculations, Field, Inverse, Pow},
+    scalar::ScalarMul,
+    traits::{Field, FiniteField, Inverse, Pow, ProjectiveCalculations},
 };
 
 use crate::schnorr::ToJsonSchnorr;
 
 pub trait ParamsForDiffieHellman {
-    type G: Pow + ToJsonSchnorr + Inverse;
+    type G: Pow + ScalarMul + ToJsonSchnorr + Inverse;
     fn get_g(&self) -> Self::G;
     fn get_q(&self) -> T;
 }
@@ -36,6 +38,9 @@ pub trait DiffieHellman {
     //     seen.len() as T + 1
     // }
     fn generate_secret_key(params: &Self::Params, random_value: T) -> T;
+    /// g multiplied by the secret key on the ladder. A `FixedBase` comb for g would be
+    /// faster, but its table indices are the bits of the scalar and it skips the zero
+    /// ones, so it is only meant for public scalars.
     fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey;
     fn compute_shared_secret(
         params: &Self::Params,
@@ -72,7 +77,7 @@ impl<const P: T> DiffieHellman for Fp<P> {
 
     fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey {
         assert_eq!(params.p, P);
-        params.g.pow(*secret_key)
+        params.g.ladder(secret_key)
     }
 
     fn compute_shared_secret(
@@ -81,7 +86,42 @@ impl<const P: T> DiffieHellman for Fp<P> {
         public_key: &Self::PublicKey,
     ) -> Self::PublicKey {
         assert_eq!(params.p, P);
-        public_key.pow(*secret_key)
+        public_key.ladder(secret_key)
+    }
+}
+
+pub struct FpMontParams<C: MontgomeryParams<N>, const N: usize> {
+    pub g: FpMont<C, N>,
+    pub q: T,
+}
+impl<C: MontgomeryParams<N>, const N: usize> ParamsForDiffieHellman for FpMontParams<C, N> {
+    type G = FpMont<C, N>;
+    fn get_g(&self) -> Self::G {
+        self.g
+    }
+    fn get_q(&self) -> T {
+        self.q
+    }
+}
+impl<C: MontgomeryParams<N>, const N: usize> DiffieHellman for FpMont<C, N> {
+    /// (G, Q), the modulus is part of the type
+    type Params = FpMontParams<C, N>;
+    type PublicKey = Self;
+
+    fn generate_secret_key(params: &Self::Params, random_value: T) -> T {
+        1 + ((random_value - 1) % (params.q - 1))
+    }
+
+    fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey {
+        params.g.ladder(secret_key)
+    }
+
+    fn compute_shared_secret(
+        _params: &Self::Params,
+        secret_key: &T,
+        public_key: &Self::PublicKey,
+    ) -> Self::PublicKey {
+        public_key.ladder(secret_key)
     }
 }
 
@@ -116,7 +156,7 @@ impl<const P: T, const K: T> DiffieHellman for Fpk<P, K> {
     fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey {
         assert_eq!(params.p, P);
         assert_eq!(params.k, K);
-        params.g.clone().pow(*secret_key)
+        params.g.ladder(secret_key)
     }
 
     fn compute_shared_secret(
@@ -126,7 +166,7 @@ impl<const P: T, const K: T> DiffieHellman for Fpk<P, K> {
     ) -> Self::PublicKey {
         assert_eq!(params.p, P);
         assert_eq!(params.k, K);
-        public_key.clone().pow(*secret_key)
+        public_key.ladder(secret_key)
     }
 }
 
@@ -159,7 +199,7 @@ impl<const M: T> DiffieHellman for F2m<M> {
 
     fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey {
         assert_eq!(params.m, M);
-        params.g.clone().pow(*secret_key)
+        params.g.ladder(secret_key)
     }
 
     fn compute_shared_secret(
@@ -168,25 +208,45 @@ impl<const M: T> DiffieHellman for F2m<M> {
         public_key: &Self::PublicKey,
     ) -> Self::PublicKey {
         assert_eq!(params.m, M);
-        public_key.clone().pow(*secret_key)
+        public_key.ladder(secret_key)
     }
 }
 
 pub struct EcPointParams<Y>
 where
     Y: Field + Pow,
-    Ec<Y>: EcCalculations<Y>,
+    Ec<Y>: ProjectiveCalculations<Y>,
     EcPoint<Y>: Add<Output = EcPoint<Y>>,
 {
-    pub a: Y,
-    pub b: Y,
+    /// Curve of g, whose shared parameters let the points be checked against it
+    /// by a pointer comparison.
+    pub ec: Ec<Y>,
     pub g: EcPoint<Y>,
     pub q: T,
 }
+impl<Y> EcPointParams<Y>
+where
+    Y: FiniteField,
+    Ec<Y>: ProjectiveCalculations<Y>,
+    EcPoint<Y>: Add<Output = EcPoint<Y>> + Neg<Output = EcPoint<Y>>,
+{
+    /// Takes q as the order of g, which means counting the points of the curve.
+    pub fn with_order_of_g(ec: Ec<Y>, g: EcPoint<Y>) -> Self {
+        let q = T::try_from(g.order()).expect("Order of g must fit in T");
+        Self { ec, g, q }
+    }
+
+    /// Returns whether g is a point of the curve and q a multiple of its order.
+    pub fn is_q_valid(&self) -> bool {
+        self.g.curve().is_none_or(|g_ec| *g_ec == self.ec)
+            && self.g.is_on_curve()
+            && self.g.mul_vartime(&self.q).is_identity()
+    }
+}
 impl<Y> ParamsForDiffieHellman for EcPointParams<Y>
 where
     Y: Field + Pow + ToJsonSchnorr,
-    Ec<Y>: EcCalculations<Y>,
+    Ec<Y>: ProjectiveCalculations<Y>,
     EcPoint<Y>: Add<Output = EcPoint<Y>> + Neg<Output = EcPoint<Y>> + Inverse,
 {
     type G = EcPoint<Y>;
@@ -200,31 +260,26 @@ where
 impl<Y> DiffieHellman for EcPoint<Y>
 where
     Y: Field + Pow + ToJsonSchnorr,
-    Ec<Y>: EcCalculations<Y>,
+    Ec<Y>: ProjectiveCalculations<Y>,
     EcPoint<Y>: Add<Output = EcPoint<Y>> + Neg<Output = EcPoint<Y>>,
 {
-    /// (A, B, (G.x, G.y), Q)
+    /// (curve, G, Q)
     type Params = EcPointParams<Y>;
     // type SecretKey = T;
     type PublicKey = Self;
 
     fn generate_secret_key(params: &Self::Params, random_value: T) -> T {
-        let EcPointParams { a, b, g, q } = params;
-        let _ec: Ec<Y> = Ec::new(a.clone(), b.clone());
-        let temp = EcPoint::new(a.clone(), b.clone(), _ec).unwrap();
-        assert!(EcPoint::match_ec(g, &temp));
-        // let q = Self::order_of_group_of_g(&_g);
+        let EcPointParams { ec, g, q } = params;
+        assert!(g.curve().is_none_or(|g_ec| g_ec == ec));
 
         1 + ((random_value - 1) % (q - 1))
     }
 
     fn compute_public_key(params: &Self::Params, secret_key: &T) -> Self::PublicKey {
-        let EcPointParams { a, b, g, q: _ } = params;
-        let _ec: Ec<Y> = Ec::new(a.clone(), b.clone());
-        let temp = EcPoint::new(a.clone(), b.clone(), _ec).unwrap();
-        assert!(EcPoint::match_ec(g, &temp));
+        let EcPointParams { ec, g, q: _ } = params;
+        assert!(g.curve().is_none_or(|g_ec| g_ec == ec));
 
-        g.clone().pow(*secret_key)
+        g.ladder(secret_key)
     }
 
     fn compute_shared_secret(
@@ -232,10 +287,15 @@ where
         secret_key: &T,
         public_key: &Self::PublicKey,
     ) -> Self::PublicKey {
-        let _ec: Ec<Y> = Ec::new(params.a.clone(), params.b.clone());
-        let temp = EcPoint::new(params.a.clone(), params.b.clone(), _ec).unwrap();
-        assert!(EcPoint::match_ec(&params.g, &temp));
-        public_key.clone().pow(*secret_key)
+        let EcPointParams { ec, g, q: _ } = params;
+        assert!(g.curve().is_none_or(|g_ec| g_ec == ec));
+        // A peer's key off the curve, or on another one, would leak the secret key
+        // through the small subgroups of that curve
+        assert!(
+            public_key.is_on_curve() && public_key.curve().is_none_or(|key_ec| key_ec == ec),
+            "Public key is not a point of the curve"
+        );
+        public_key.ladder(secret_key)
     }
 }
 
@@ -314,6 +374,27 @@ mod tests {
         assert_eq!(b_res.2, Fp::<P>::new(2));
     }
 
+    #[test]
+    fn test_dh_fp_mont() {
+        #[derive(Debug)]
+        struct P23;
+        impl MontgomeryParams<1> for P23 {
+            const MODULUS: [u64; 1] = [23];
+        }
+        let g = FpMont::<P23, 1>::new(5);
+        let q = 22;
+
+        let params = FpMontParams { g, q };
+
+        let (a_res, b_res) = assert_dh_exchange::<FpMont<P23, 1>>(&params, 6, 15);
+
+        // Same example as for Fp
+        assert_eq!(a_res.1, FpMont::new(8));
+        assert_eq!(a_res.2, FpMont::new(2));
+        assert_eq!(b_res.1, FpMont::new(19));
+        assert_eq!(b_res.2, FpMont::new(2));
+    }
+
     #[test]
     fn test_dh_fpk() {
         const P: T = 23;
@@ -352,10 +433,61 @@ mod tests {
 
         let gx = Y::from(5);
         let gy = Y::from(1);
-        let g = EcPoint::new(gx, gy, Ec::new(a, b)).unwrap();
-
-        let params = EcPointParams { a, b, g, q: 15 };
+        let ec = Ec::new(a, b);
+        let g = EcPoint::new(gx, gy, ec.clone()).unwrap();
+
+        let guessed = EcPointParams {
+            ec: ec.clone(),
+            g: g.clone(),
+            q: 15,
+        };
+        assert!(!guessed.is_q_valid());
+        let params = EcPointParams::with_order_of_g(ec, g);
+        assert_eq!(params.q, 19);
+        assert!(params.is_q_valid());
 
         assert_dh_exchange::<EcPoint<Y>>(&params, 3, 7);
     }
+
+    #[test]
+    #[should_panic(expected = "Public key is not a point of the curve")]
+    fn test_dh_rejects_point_off_curve() {
+        type Y = Fp<17>;
+        let ec = Ec::new(Y::from(2), Y::from(2));
+        let g = EcPoint::new(Y::from(5), Y::from(1), ec.clone()).unwrap();
+        let params = EcPointParams::with_order_of_g(ec.clone(), g);
+        // Built directly, so nothing checked it
+        let invalid = EcPoint::Point {
+            x: Y::from(5),
+            y: Y::from(2),
+            ec,
+        };
+
+        EcPoint::compute_shared_secret(&params, &3, &invalid);
+    }
+
+    #[test]
+    #[should_panic(expected = "Public key is not a point of the curve")]
+    fn test_dh_rejects_point_on_other_curve() {
+        type Y = Fp<17>;
+        let ec = Ec::new(Y::from(2), Y::from(2));
+        let g = EcPoint::new(Y::from(5), Y::from(1), ec.clone()).unwrap();
+        let params = EcPointParams::with_order_of_g(ec, g);
+        let other = EcPoint::new(Y::from(0), Y::from(1), Ec::new(Y::from(1), Y::from(1))).unwrap();
+
+        EcPoint::compute_shared_secret(&params, &3, &other);
+    }
+
+    #[test]
+    fn test_dh_named_curve() {
+        let curve = lab2::curves::secp128r1();
+        let params = EcPointParams {
+            ec: curve.ec,
+            g: curve.g,
+            q: T::try_from(&curve.n).unwrap(),
+        };
+        assert!(params.is_q_valid());
+
+        _ = assert_dh_exchange::<EcPoint<_>>(&params, 0xdead_beef, u128::MAX / 3);
+    }
 }
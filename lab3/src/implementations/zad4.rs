@@ -3,8 +3,10 @@ use lab2::{
     elliptic_curve::{Ec, EcPoint},
     f2m::{F2m, bit::Bits8},
     fp::Fp,
+    fp_mont::{FpMont, MontgomeryParams},
     fpk::Fpk,
-    traits::{Field, Inverse, Pow},
+    scalar::{ScalarMul, shamir},
+    traits::{Field, Inverse},
 };
 use sha2::{Digest, Sha256};
 
@@ -28,6 +30,13 @@ impl<const P: T> ToJsonSchnorr for Fp<P> {
     }
 }
 
+impl<C: MontgomeryParams<N>, const N: usize> ToJsonSchnorr for FpMont<C, N> {
+    fn encode(&self) -> String {
+        let p_len = 2 * Self::modulus_bits().div_ceil(8) as usize;
+        format!("\"{:0>width$}\"", format!("{:X}", self), width = p_len)
+    }
+}
+
 impl<const P: T, const K: T> ToJsonSchnorr for Fpk<P, K> {
     fn encode(&self) -> String {
         let mut result = "[".to_string();
@@ -95,7 +104,9 @@ pub fn sign<Y: DiffieHellman + ToJsonSchnorr>(
     m: &str,
 ) -> (T, T, String) {
     let x = Y::generate_secret_key(params, random);
-    let r = params.get_g().pow(random);
+    // The nonce is secret, so the same reasoning as in `compute_public_key` keeps this
+    // on the ladder rather than a `FixedBase` comb
+    let r = params.get_g().ladder(&random);
     let mut input = r.encode();
     input.extend(m.chars());
     let e: Vec<T> = Sha256::digest(input)
@@ -123,8 +134,9 @@ pub fn verify<Y: DiffieHellman + ToJsonSchnorr>(
     m: &str,
 ) -> bool {
     let x = Y::generate_secret_key(params, random);
-    let y = params.get_g().pow(x).inv();
-    let r_v = params.get_g().pow(s) * y.pow(e);
+    let y = params.get_g().ladder(&x).inv();
+    // s and e are public, so the faster variable time multiplication is fine
+    let r_v = shamir((&params.get_g(), &s), (&y, &e));
     let mut input = r_v.encode();
     input.extend(m.chars());
     let e_v: Vec<T> = Sha256::digest(input)
@@ -145,7 +157,7 @@ pub fn verify<Y: DiffieHellman + ToJsonSchnorr>(
 mod tests {
     use lab2::polynomials::Polynomial;
 
-    use crate::diffie_hellman::FpParams;
+    use crate::diffie_hellman::{EcPointParams, FpParams};
 
     use super::*;
 
@@ -155,6 +167,17 @@ mod tests {
         assert_eq!(fp.encode(), r#""000011""#);
     }
 
+    #[test]
+    fn test_fp_mont() {
+        #[derive(Debug)]
+        struct P65537;
+        impl MontgomeryParams<1> for P65537 {
+            const MODULUS: [u64; 1] = [65537];
+        }
+        let fp: FpMont<P65537, 1> = FpMont::new(17);
+        assert_eq!(fp.encode(), Fp::<65537>::new(17).encode());
+    }
+
     #[test]
     fn test_fpk() {
         let modulo: Polynomial<Fp<17>> = Polynomial::new_from_slice(&[6, 4, 5, 1]);
@@ -179,7 +202,7 @@ mod tests {
         let x = Fp::<17>::new(3);
         let y = Fp::<17>::new(5);
         let ec = Ec::new(x.clone(), y.clone());
-        let point = EcPoint::new(x, y, ec).unwrap();
+        let point = EcPoint::new_unchecked(x, y, ec);
 
         assert_eq!(point.encode(), r#"{"x":"03","y":"05"}"#)
     }
@@ -198,6 +221,20 @@ mod tests {
         assert_eq!(value, expected);
     }
 
+    #[test]
+    fn test_sign_and_verify_on_named_curve() {
+        let curve = lab2::curves::sect113r1();
+        let params = EcPointParams {
+            ec: curve.ec,
+            g: curve.g,
+            q: T::try_from(&curve.n).unwrap(),
+        };
+        assert!(params.is_q_valid());
+        let (s, e, _) = sign::<EcPoint<F2m<113>>>(&params, 12345, "Alice");
+        assert!(verify::<EcPoint<F2m<113>>>(&params, 12345, (s, e), "Alice"));
+        assert!(!verify::<EcPoint<F2m<113>>>(&params, 12345, (s, e), "Bob"));
+    }
+
     #[test]
     fn test_sign_and_verify() {
         let random = 15;
@@ -11,3 +11,8 @@ pub mod diffie_hellman {
 pub mod schnorr {
     pub use super::implementations::zad4::*;
 }
+
+pub mod discrete_log {
+    pub use super::implementations::dlog::*;
+    pub use super::implementations::index_calculus::*;
+}